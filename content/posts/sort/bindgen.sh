@@ -1,3 +1,5 @@
 #!/bin/sh
 
-bindgen ./c-src/sort.h > ./src/csort_bind.rs
+bindgen ./c-src/sort.h \
+  --allowlist-function '.*_(i32|i64|u32|u64|f32|f64|keyed|counted)|take_(comparisons|writes|swaps)' \
+  > ./src/csort_bind.rs
@@ -1,6 +1,8 @@
 use std::env;
 
 fn main() {
+    println!("cargo:rerun-if-changed=c-src");
+
     let mut cc_build = cc::Build::new();
     env::var("DEP_OPENMP_FLAG")
         .unwrap()
@@ -18,6 +20,7 @@ fn main() {
         .file("./c-src/std-qsort.c")
         .file("./c-src/radix-sort.c")
         .file("./c-src/quick-sort.c")
+        .file("./c-src/counted.c")
         .flag("-fopenmp")
         .compile("csort");
 
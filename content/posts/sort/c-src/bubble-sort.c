@@ -1,15 +1,8 @@
+#ifndef TEMPLATE
 #include "sort.h"
 
-static inline void bubble_pass(unsigned len, int array[len]) {
-  for (unsigned i = 1; i < len; i++)
-    if (array[i - 1] > array[i])
-      SWAP(array[i - 1], array[i]);
-}
-
-void bubble_sort(unsigned len, int array[len]) {
-  for (unsigned i = len; i > 1; i--)
-    bubble_pass(i, array);
-}
+#define TEMPLATE "bubble-sort.c"
+#include "each-type.h"
 
 /* #define TEST */
 #ifdef TEST
@@ -18,13 +11,13 @@ void bubble_sort(unsigned len, int array[len]) {
 
 void test_empty() {
   int array[] = {};
-  bubble_sort(0, array);
+  bubble_sort_i32(0, array);
 }
 
 void test_sort() {
   int random[] = {1, 3, 5, 0, 3, -1, 4, 8};
   DISPLAY_ARRAY(random);
-  bubble_sort(ARRAY_LEN(random), random);
+  bubble_sort_i32(ARRAY_LEN(random), random);
   DISPLAY_ARRAY(random);
 }
 
@@ -34,3 +27,18 @@ int main() {
 }
 
 #endif /* ifdef TEST */
+
+#else /* TEMPLATE */
+
+static inline void NAME(bubble_pass)(size_t len, T array[len]) {
+  for (size_t i = 1; i < len; i++)
+    if (LESS(array[i], array[i - 1]))
+      SWAP(array[i - 1], array[i]);
+}
+
+void NAME(bubble_sort)(size_t len, T array[len]) {
+  for (size_t i = len; i > 1; i--)
+    NAME(bubble_pass)(i, array);
+}
+
+#endif /* TEMPLATE */
@@ -0,0 +1,13 @@
+#include "sort.h"
+
+atomic_size_t counted_comparisons = 0;
+atomic_size_t counted_writes = 0;
+atomic_size_t counted_swaps = 0;
+
+size_t take_comparisons(void) {
+  return atomic_exchange(&counted_comparisons, 0);
+}
+
+size_t take_writes(void) { return atomic_exchange(&counted_writes, 0); }
+
+size_t take_swaps(void) { return atomic_exchange(&counted_swaps, 0); }
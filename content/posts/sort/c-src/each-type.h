@@ -0,0 +1,107 @@
+// Include the file named by TEMPLATE once for every element type, with
+// - T the element type and U the unsigned type of the same size,
+// - NAME(f) the name f suffixed with the type, such as quick_sort_i64,
+// - LESS(a, b) the order of T, see sort.h,
+// - WROTE(n) and SWAPPED() to report stored elements and swaps, see sort.h,
+// - KEY_BYTES the number of low bytes of the key which order T.
+//
+// A source file instantiates itself with
+//
+// #ifndef TEMPLATE
+// // shared code
+// #define TEMPLATE "this-file.c"
+// #include "each-type.h"
+// #else
+// // code using T, NAME and LESS
+// #endif
+
+#define LESS(a, b) NAME(less)(a, b)
+#define WROTE(n) NAME(wrote)(n)
+#define SWAPPED() NAME(swapped)()
+
+#define T int32_t
+#define U uint32_t
+#define NAME(f) f##_i32
+#define KEY_BYTES sizeof(T)
+#include TEMPLATE
+#undef T
+#undef U
+#undef NAME
+#undef KEY_BYTES
+
+#define T int64_t
+#define U uint64_t
+#define NAME(f) f##_i64
+#define KEY_BYTES sizeof(T)
+#include TEMPLATE
+#undef T
+#undef U
+#undef NAME
+#undef KEY_BYTES
+
+#define T uint32_t
+#define U uint32_t
+#define NAME(f) f##_u32
+#define KEY_BYTES sizeof(T)
+#include TEMPLATE
+#undef T
+#undef U
+#undef NAME
+#undef KEY_BYTES
+
+#define T uint64_t
+#define U uint64_t
+#define NAME(f) f##_u64
+#define KEY_BYTES sizeof(T)
+#include TEMPLATE
+#undef T
+#undef U
+#undef NAME
+#undef KEY_BYTES
+
+#define T float
+#define U uint32_t
+#define NAME(f) f##_f32
+#define KEY_BYTES sizeof(T)
+#include TEMPLATE
+#undef T
+#undef U
+#undef NAME
+#undef KEY_BYTES
+
+#define T double
+#define U uint64_t
+#define NAME(f) f##_f64
+#define KEY_BYTES sizeof(T)
+#include TEMPLATE
+#undef T
+#undef U
+#undef NAME
+#undef KEY_BYTES
+
+// u64 ordered by its high half, only to test stability, see sort.h
+#define T uint64_t
+#define U uint64_t
+#define NAME(f) f##_keyed
+#define KEY_BYTES 4
+#include TEMPLATE
+#undef T
+#undef U
+#undef NAME
+#undef KEY_BYTES
+
+// keyed with counted comparisons, only to measure the sorts, see sort.h
+#define T uint64_t
+#define U uint64_t
+#define NAME(f) f##_counted
+#define KEY_BYTES 4
+#include TEMPLATE
+#undef T
+#undef U
+#undef NAME
+#undef KEY_BYTES
+
+#undef LESS
+#undef WROTE
+#undef SWAPPED
+#undef TEMPLATE
@@ -1,39 +1,8 @@
+#ifndef TEMPLATE
 #include "sort.h"
 
-// user should ensure array is sorted.
-static inline int insert_by_ord(unsigned len, int array[len], int element) {
-  if (len == 0)
-    return element;
-
-  int last = array[len - 1];
-  if (element >= last)
-    // element is greater than or equal array max
-    return element;
-
-  // sliding window 2
-  for (int i = len - 2; i >= 0; i--) {
-    int wave = array[i];
-
-    if (element >= wave) {
-      // element meet the first less than or equal self
-      array[i + 1] = element;
-      return last;
-    }
-
-    array[i + 1] = wave;
-  }
-
-  // element is less than array min
-  array[0] = element;
-  return last;
-}
-
-void insertion_sort(unsigned len, int array[len]) {
-  for (unsigned i = 0; i < len; i++) {
-    int tmp = insert_by_ord(i, array, array[i]);
-    array[i] = tmp;
-  }
-}
+#define TEMPLATE "insertion-sort.c"
+#include "each-type.h"
 
 /* #define TEST */
 #ifdef TEST
@@ -50,8 +19,8 @@ void test_insert_array() {
   printf("insert ");
   DISPLAY_ARRAY(insert);
 
-  for (unsigned i = 0; i < ARRAY_LEN(insert); i++) {
-    tmp = insert_by_ord(i, array, insert[i]);
+  for (size_t i = 0; i < ARRAY_LEN(insert); i++) {
+    tmp = insert_by_ord_i32(i, array, insert[i]);
     array[i] = tmp;
     DISPLAY_ARRAY(array);
   }
@@ -61,7 +30,7 @@ void test_insert_sort() {
   puts("test_insert_sort:");
   int array[] = {1, 3, 5, 0, 3, 4, 4, 8};
   DISPLAY_ARRAY(array);
-  insertion_sort(ARRAY_LEN(array), array);
+  insertion_sort_i32(ARRAY_LEN(array), array);
   DISPLAY_ARRAY(array);
 }
 
@@ -71,7 +40,7 @@ void test_insert_sort() {
 
 #define COPY_ARRAY(DEST, SRC)                                                  \
   int DEST[ARRAY_LEN(SRC)];                                                    \
-  for (unsigned i = 0; i < ARRAY_LEN(SRC); i++)                                \
+  for (size_t i = 0; i < ARRAY_LEN(SRC); i++)                                  \
     DEST[i] = SRC[i];
 
 int compare(const void *a, const void *b) { return *(int *)a - *(int *)b; }
@@ -80,10 +49,10 @@ void test_insert_random() {
   COPY_ARRAY(insert_array, random_data);
   COPY_ARRAY(std_array, random_data);
 
-  insertion_sort(ARRAY_LEN(random_data), insert_array);
+  insertion_sort_i32(ARRAY_LEN(random_data), insert_array);
   qsort(std_array, ARRAY_LEN(random_data), sizeof(int), compare);
 
-  for (unsigned i = 0; i < ARRAY_LEN(random_data); i++)
+  for (size_t i = 0; i < ARRAY_LEN(random_data); i++)
     if (insert_array[i] != std_array[i])
       puts("test_insert_random failed");
 }
@@ -97,3 +66,46 @@ int main() {
 }
 
 #endif /* ifdef TEST */
+
+#else /* TEMPLATE */
+
+// user should ensure array is sorted.
+static inline T NAME(insert_by_ord)(size_t len, T array[len], T element) {
+  if (len == 0)
+    return element;
+
+  T last = array[len - 1];
+  if (!LESS(element, last))
+    // element is greater than or equal array max
+    return element;
+
+  // sliding window 2
+  for (size_t i = len - 1; i > 0; i--) {
+    T wave = array[i - 1];
+
+    if (!LESS(element, wave)) {
+      // element meet the first less than or equal self
+      array[i] = element;
+      WROTE(1);
+      return last;
+    }
+
+    array[i] = wave;
+    WROTE(1);
+  }
+
+  // element is less than array min
+  array[0] = element;
+  WROTE(1);
+  return last;
+}
+
+void NAME(insertion_sort)(size_t len, T array[len]) {
+  for (size_t i = 0; i < len; i++) {
+    T tmp = NAME(insert_by_ord)(i, array, array[i]);
+    array[i] = tmp;
+    WROTE(1);
+  }
+}
+
+#endif /* TEMPLATE */
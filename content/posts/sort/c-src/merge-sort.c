@@ -1,67 +1,10 @@
+#ifndef TEMPLATE
 #include "sort.h"
 #include <stdbool.h>
+#include <stdlib.h>
 
-void merge_sort_rec(unsigned len, int array[len]) {
-  if (len <= 1)
-    return;
-
-  unsigned half = len / 2;
-  merge_sort_rec(half, array);
-  merge_sort_rec(len - half, &array[half]);
-
-  merge_two_sorted_array(len, array, half);
-}
-
-// return block_size
-unsigned parallel_sort_blocks(unsigned len, int array[len]) {
-  const unsigned DEFAULT_BLOCK_SIZE = 128;
-  const unsigned MAX_BLOCKS = 64;
-  unsigned block_size = len / DEFAULT_BLOCK_SIZE > MAX_BLOCKS
-                            ? len / MAX_BLOCKS
-                            : DEFAULT_BLOCK_SIZE;
-  unsigned blocks = len / block_size;
-
-#pragma omp parallel for
-  for (unsigned i = 0; i < blocks; i++)
-    merge_sort_rec(block_size, &array[i * block_size]);
-
-  if (len > block_size * blocks)
-    // the last block is not full.
-    merge_sort_rec(len - (blocks * block_size), &array[blocks * block_size]);
-
-  return block_size;
-}
-
-void merge_adjacent_blocks(unsigned len, int array[len], unsigned block_size) {
-  if (block_size >= len)
-    // Only have one block and have sorted.
-    return;
-
-  unsigned blocks = len / block_size; // blocks >= 1
-  for (unsigned i = 0; i < blocks / 2; i++)
-    merge_two_sorted_array(block_size * 2, &array[i * 2 * block_size],
-                           block_size);
-
-  if (blocks % 2 == 1 && len > block_size * blocks) {
-    // The last block is not full and remain the second last block not merge.
-    unsigned lave = len - block_size * blocks;
-    merge_two_sorted_array(block_size + lave, &array[len - lave - block_size],
-                           block_size);
-  }
-}
-
-void merge_sort_parallel(unsigned len, int array[len]) {
-  unsigned block_size = parallel_sort_blocks(len, array);
-  for (; block_size <= len; block_size *= 2)
-    merge_adjacent_blocks(len, array, block_size);
-}
-
-void merge_sort_adjacent_blocks(unsigned len, int array[len]) {
-  for (unsigned block_size = 1; block_size <= len; block_size *= 2)
-    merge_adjacent_blocks(len, array, block_size);
-}
-
-void merge_sort(unsigned len, int array[len]) { merge_sort_rec(len, array); }
+#define TEMPLATE "merge-sort.c"
+#include "each-type.h"
 
 /* #define TEST */
 #ifdef TEST
@@ -73,7 +16,7 @@ void merge_sort(unsigned len, int array[len]) { merge_sort_rec(len, array); }
     puts(#Ident ":");                                                          \
     int array[] = __VA_ARGS__;                                                 \
     DISPLAY_ARRAY(array);                                                      \
-    merge_sort(ARRAY_LEN(array), array);                                       \
+    merge_sort_i32(ARRAY_LEN(array), array, NULL);                             \
     DISPLAY_ARRAY(array);                                                      \
   }
 
@@ -100,3 +43,100 @@ int main() {
 }
 
 #endif /* ifdef TEST */
+
+#else /* TEMPLATE */
+
+// buffer holds len elements
+void NAME(merge_sort_rec)(size_t len, T array[len], T buffer[len]) {
+  if (len <= 1)
+    return;
+
+  size_t half = len / 2;
+  NAME(merge_sort_rec)(half, array, buffer);
+  NAME(merge_sort_rec)(len - half, &array[half], &buffer[half]);
+
+  NAME(merge_two_sorted_array)(len, array, half, buffer);
+}
+
+// return the buffer, or a heap buffer of len elements if it is NULL
+static T *NAME(scratch)(size_t len, T *buffer) {
+  if (buffer != NULL)
+    return buffer;
+
+  return malloc((len > 0 ? len : 1) * sizeof(T));
+}
+
+// return block_size
+size_t NAME(parallel_sort_blocks)(size_t len, T array[len], T buffer[len]) {
+  const size_t DEFAULT_BLOCK_SIZE = 128;
+  const size_t MAX_BLOCKS = 64;
+  size_t block_size = len / DEFAULT_BLOCK_SIZE > MAX_BLOCKS
+                          ? len / MAX_BLOCKS
+                          : DEFAULT_BLOCK_SIZE;
+  size_t blocks = len / block_size;
+
+#pragma omp parallel for
+  for (size_t i = 0; i < blocks; i++)
+    NAME(merge_sort_rec)(block_size, &array[i * block_size],
+                         &buffer[i * block_size]);
+
+  if (len > block_size * blocks)
+    // the last block is not full.
+    NAME(merge_sort_rec)(len - (blocks * block_size),
+                         &array[blocks * block_size],
+                         &buffer[blocks * block_size]);
+
+  return block_size;
+}
+
+void NAME(merge_adjacent_blocks)(size_t len, T array[len], size_t block_size,
+                                 T buffer[len]) {
+  if (block_size >= len)
+    // Only have one block and have sorted.
+    return;
+
+  size_t blocks = len / block_size; // blocks >= 1
+  for (size_t i = 0; i < blocks / 2; i++)
+    NAME(merge_two_sorted_array)(block_size * 2, &array[i * 2 * block_size],
+                                 block_size, buffer);
+
+  if (blocks % 2 == 1 && len > block_size * blocks) {
+    // The last block is not full and remain the second last block not merge.
+    size_t lave = len - block_size * blocks;
+    NAME(merge_two_sorted_array)(block_size + lave,
+                                 &array[len - lave - block_size], block_size,
+                                 buffer);
+  }
+}
+
+SortError NAME(merge_sort_parallel)(size_t len, T array[len], T *buffer) {
+  T *data = NAME(scratch)(len, buffer);
+  if (data == NULL)
+    return SORT_NO_MEMORY;
+
+  size_t block_size = NAME(parallel_sort_blocks)(len, array, data);
+  for (; block_size <= len; block_size *= 2)
+    NAME(merge_adjacent_blocks)(len, array, block_size, data);
+
+  if (buffer == NULL)
+    free(data);
+  return SORT_OK;
+}
+
+void NAME(merge_sort_adjacent_blocks)(size_t len, T array[len], T buffer[len]) {
+  for (size_t block_size = 1; block_size <= len; block_size *= 2)
+    NAME(merge_adjacent_blocks)(len, array, block_size, buffer);
+}
+
+SortError NAME(merge_sort)(size_t len, T array[len], T *buffer) {
+  T *data = NAME(scratch)(len, buffer);
+  if (data == NULL)
+    return SORT_NO_MEMORY;
+  NAME(merge_sort_rec)(len, array, data);
+
+  if (buffer == NULL)
+    free(data);
+  return SORT_OK;
+}
+
+#endif /* TEMPLATE */
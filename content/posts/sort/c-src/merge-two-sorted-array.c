@@ -1,61 +1,115 @@
+#ifndef TEMPLATE
+#include "sort.h"
 #include <stdbool.h>
+#include <stdlib.h>
 #include <string.h>
 
-struct Stack {
-  int *data;
-  unsigned len;
-};
+#define NEW_STACK(IDENT, BUF)                                                  \
+  struct NAME(Stack) IDENT = {.data = BUF, .len = 0};
+
+#define TEMPLATE "merge-two-sorted-array.c"
+#include "each-type.h"
+
+/* #define TEST */
+#ifdef TEST
+
+#include <stdio.h>
+
+void test_stack() {
+  int buffer[5];
+  struct Stack_i32 stack = {.data = buffer, .len = 0};
+
+  stack_push_i32(&stack, 1);
+  stack_push_i32(&stack, 2);
+  stack_push_i32(&stack, 3);
+  stack_push_i32(&stack, 4);
+  stack_push_i32(&stack, 5);
+
+  int array[5];
+  stack_drain_i32(&stack, array, 5);
+  for (int i = 0; i < 5; i++) {
+    printf("%d ", array[i]);
+  }
+  printf("\n");
+}
+
+int main() {
+  test_stack();
+  int array[] = {};
+
+  merge_two_sorted_array_i32(0, array, 0, NULL);
+
+  for (int i = 0; i < 1; i++) {
+    printf("%d ", array[i]);
+  }
+  printf("\n");
+}
+
+#endif /* ifdef TEST */
+
+#else /* TEMPLATE */
 
-#define NEW_STACK(IDENT, LEN)                                                  \
-  int __stack_##IDENT##_buf_size_##LEN[LEN]; /* stack cap is len */            \
-  struct Stack IDENT = {.data = __stack_##IDENT##_buf_size_##LEN, .len = 0};
+struct NAME(Stack) {
+  T *data;
+  size_t len;
+};
 
 // user should ensure stack->len < stack buf size
-static inline void stack_push(struct Stack *stack, int value) {
+static inline void NAME(stack_push)(struct NAME(Stack) *stack, T value) {
   stack->data[stack->len++] = value;
+  WROTE(1);
 }
 
 // user should ensure stack->len > 0
-static inline int stack_pop(struct Stack *stack) {
+static inline T NAME(stack_pop)(struct NAME(Stack) *stack) {
   return stack->data[--stack->len];
 }
 
 // user should ensure stack->len < stack buf size
 /* Not use
-static void stack_extend(struct Stack *stack, const int *slice, unsigned len) {
-  memcpy(stack->data + stack->len, slice, len * sizeof(int));
+static void NAME(stack_extend)(struct NAME(Stack) *stack, const T *slice,
+                               size_t len) {
+  memcpy(stack->data + stack->len, slice, len * sizeof(T));
   stack->len += len;
 }
 */
 
 // user should ensure stack->len > 0
 // move data from stack->data[stack->len - len..stack->len] to slice
-static void stack_drain(struct Stack *stack, int *slice, unsigned len) {
-  memcpy(slice, &stack->data[stack->len - len], len * sizeof(int));
+static void NAME(stack_drain)(struct NAME(Stack) *stack, T *slice, size_t len) {
+  memcpy(slice, &stack->data[stack->len - len], len * sizeof(T));
+  WROTE(len);
   stack->len -= len;
 }
 
-void merge_two_sorted_array(unsigned len, int array[len], unsigned split) {
-  if (len <= 1 || split >= len || split == 0)
-    return;
+SortError NAME(merge_two_sorted_array)(size_t len, T array[len], size_t split,
+                                       T *buffer) {
+  if (split > len)
+    return SORT_BAD_SPLIT;
+  if (len <= 1 || split == len || split == 0)
+    return SORT_OK;
 
-  NEW_STACK(stack, len);
+  // a stack of len elements would overflow the C stack for large arrays
+  T *data = buffer != NULL ? buffer : malloc(len * sizeof(T));
+  if (data == NULL)
+    return SORT_NO_MEMORY;
+  NEW_STACK(stack, data);
 
-  int *left = array;
-  int *divide = &array[split];
-  int *right = divide;
-  int *end = &array[len];
+  T *left = array;
+  T *divide = &array[split];
+  T *right = divide;
+  T *end = &array[len];
 
   /* while (left < divide && right < end) { */
   while (true) {
-    if (*left <= *right) {
-      stack_push(&stack, *left);
+    if (!LESS(*right, *left)) {
+      NAME(stack_push)(&stack, *left);
       left += 1;
 
       if (left >= divide)
         break;
     } else {
-      stack_push(&stack, *right);
+      NAME(stack_push)(&stack, *right);
       right += 1;
 
       if (right >= end) {
@@ -66,51 +120,23 @@ void merge_two_sorted_array(unsigned len, int array[len], unsigned split) {
          * if (left < divide)
          * // There is still something left on the left side.
          * // The reason for exiting is that the right side has been traversed.
-         * stack_extend(&stack, left, divide - left);
+         * NAME(stack_extend)(&stack, left, divide - left);
          */
-        unsigned lave = divide - left;
-        memcpy(&array[len - lave], left, lave * sizeof(int));
+        size_t lave = divide - left;
+        // the remaining left elements can overlap their destination
+        memmove(&array[len - lave], left, lave * sizeof(T));
+        WROTE(lave);
 
         break;
       }
     }
   }
 
-  stack_drain(&stack, array, stack.len);
-}
-
-/* #define TEST */
-#ifdef TEST
+  NAME(stack_drain)(&stack, array, stack.len);
 
-#include <stdio.h>
-
-void test_stack() {
-  NEW_STACK(stack, 5);
-
-  stack_push(&stack, 1);
-  stack_push(&stack, 2);
-  stack_push(&stack, 3);
-  stack_push(&stack, 4);
-  stack_push(&stack, 5);
-
-  int array[5];
-  stack_drain(&stack, array, 5);
-  for (int i = 0; i < 5; i++) {
-    printf("%d ", array[i]);
-  }
-  printf("\n");
-}
-
-int main() {
-  test_stack();
-  int array[] = {};
-
-  merge_two_sorted_array(0, array, 0);
-
-  for (int i = 0; i < 1; i++) {
-    printf("%d ", array[i]);
-  }
-  printf("\n");
+  if (buffer == NULL)
+    free(data);
+  return SORT_OK;
 }
 
-#endif /* ifdef TEST */
+#endif /* TEMPLATE */
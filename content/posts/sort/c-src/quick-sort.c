@@ -1,27 +1,8 @@
+#ifndef TEMPLATE
 #include "sort.h"
 
-void quick_sort(unsigned len, int array[len]) {
-  if (len <= 1)
-    return;
-
-  int pivot = array[0]; // take first as pivot
-  int *left = &array[1];
-  int *right = &array[len];
-
-  while (left < right) {
-    if (*left <= pivot) {
-      left += 1;
-    } else {
-      right -= 1;
-      SWAP(*left, *right);
-    }
-  }
-
-  SWAP(*(left - 1), array[0]);
-
-  quick_sort(left - array - 1, array);
-  quick_sort(array + len - right, right);
-}
+#define TEMPLATE "quick-sort.c"
+#include "each-type.h"
 
 /* #define TEST */
 #ifdef TEST
@@ -31,7 +12,7 @@ void quick_sort(unsigned len, int array[len]) {
 void test_bias() {
   int array[] = {1, 3, 5, 0, 3, 4, 4, 8};
   display_array(8, array);
-  quick_sort(8, array);
+  quick_sort_i32(8, array);
   // array = {0, 1, 3, 3, 4, 4, 5, 8}
   display_array(8, array);
 }
@@ -39,10 +20,37 @@ void test_bias() {
 void test_rev() {
   int array[] = {5, 4, 3, 2, 1};
   DISPLAY_ARRAY(array);
-  quick_sort(5, array);
+  quick_sort_i32(5, array);
   DISPLAY_ARRAY(array);
 }
 
 int main(int argc, char *argv[]) { test_bias(); }
 
 #endif /* ifdef TEST */
+
+#else /* TEMPLATE */
+
+void NAME(quick_sort)(size_t len, T array[len]) {
+  if (len <= 1)
+    return;
+
+  T pivot = array[0]; // take first as pivot
+  T *left = &array[1];
+  T *right = &array[len];
+
+  while (left < right) {
+    if (!LESS(pivot, *left)) {
+      left += 1;
+    } else {
+      right -= 1;
+      SWAP(*left, *right);
+    }
+  }
+
+  SWAP(*(left - 1), array[0]);
+
+  NAME(quick_sort)(left - array - 1, array);
+  NAME(quick_sort)(array + len - right, right);
+}
+
+#endif /* TEMPLATE */
@@ -1,13 +1,8 @@
+#ifndef TEMPLATE
 #include "sort.h"
-#include <limits.h>
 #include <stdio.h>
 #include <stdlib.h>
 
-typedef struct LinkNode {
-  unsigned data;
-  struct LinkNode *next;
-} LinkNode;
-
 typedef struct LinkList {
   LinkNode *head;
   LinkNode *tail;
@@ -15,7 +10,7 @@ typedef struct LinkList {
 
 static inline void linklist_display(LinkList list) {
   for (LinkNode *iter = list.head; iter != NULL; iter = iter->next)
-    printf("%u ", iter->data);
+    printf("%llu ", (unsigned long long)iter->data);
   printf("\n");
 }
 
@@ -44,32 +39,10 @@ static inline LinkNode *linklist_push_one(LinkList *list, LinkNode *element) {
   return next;
 }
 
-static inline LinkList array2linklist(unsigned len, const unsigned array[len],
-                                      LinkNode buf[len]) {
-  if (len == 0)
-    return (LinkList){.head = NULL, .tail = NULL};
-
-  for (unsigned i = 0; i < len; i++) {
-    buf[i].data = array[i];
-    buf[i].next = &buf[i + 1];
-  }
-  buf[len - 1].next = NULL;
-
-  return (LinkList){.head = &buf[0], .tail = &buf[len - 1]};
-}
-
-/// user should ensure list len equal len.
-static inline void linklist2array(unsigned len, const LinkList list,
-                                  unsigned array[len]) {
-  unsigned i = 0;
-  for (LinkNode *iter = list.head; iter != NULL; iter = iter->next)
-    array[i++] = iter->data;
-}
-
 /// Divide the linked list into base linked lists, and distribute the result
 /// of dividing element by offset modulo base into base linked lists.
 /// Then merge all base linked lists into one.
-static inline LinkList radix_split_and_merge(LinkList list, unsigned offset,
+static inline LinkList radix_split_and_merge(LinkList list, uint64_t offset,
                                              unsigned base) {
   // create base linked lists bucket.
   LinkList bucket[base];
@@ -92,66 +65,106 @@ static inline LinkList radix_split_and_merge(LinkList list, unsigned offset,
   return result;
 }
 
+#define TEMPLATE "radix-sort.c"
+#include "each-type.h"
+
+/* #define TEST */
+#ifdef TEST
+
+#include "test-utils.c"
+#include <limits.h>
+
+void test_bias() {
+  int array[] = {-1, 8, 19, -3, 996, INT_MIN, INT_MAX, 0, 1, 2, 3, 4, 5, 6, 7};
+  radix_lsd_sort_i32(ARRAY_LEN(array), array, NULL);
+  DISPLAY_ARRAY(array);
+}
+
+int main(int argc, char *argv[]) {
+  test_bias();
+  return EXIT_SUCCESS;
+}
+
+#endif /* ifdef TEST */
+
+#else /* TEMPLATE */
+
+// copy the keys of the elements to a linked list in buf
+static inline LinkList NAME(array2linklist)(size_t len, const T array[len],
+                                            LinkNode buf[len]) {
+  if (len == 0)
+    return (LinkList){.head = NULL, .tail = NULL};
+
+  for (size_t i = 0; i < len; i++) {
+    buf[i].data = NAME(key)(array[i]);
+    buf[i].next = &buf[i + 1];
+  }
+  buf[len - 1].next = NULL;
+  WROTE(len);
+
+  return (LinkList){.head = &buf[0], .tail = &buf[len - 1]};
+}
+
+/// user should ensure list len equal len.
+static inline void NAME(linklist2array)(size_t len, const LinkList list,
+                                        T array[len]) {
+  size_t i = 0;
+  for (LinkNode *iter = list.head; iter != NULL; iter = iter->next)
+    array[i++] = NAME(from_key)(iter->data);
+  WROTE(len);
+}
+
 /// Radix LSD Sort with base and number of keys
 ///
-/// Split the element into `num_of_keys` keys no greater than `base`
+/// Split the key of the element (see sort.h) into `num_of_keys` keys
+/// no greater than `base`
+///
+/// `buffer` holds `len` nodes, or is NULL to allocate them on the heap.
 ///
 /// # Warning
 ///
-/// The element of `array` must be no greater than `num_of_keys * base`
-static inline void radix_lsd_sort_with(unsigned len, unsigned array[len],
-                                       unsigned base, unsigned num_of_keys) {
-  LinkNode node_buf[len]; // create a buffer for linklist.
-  LinkList list = array2linklist(len, array, node_buf); // copy to linklist
+/// The key of the element must be no greater than `num_of_keys * base`
+static inline SortError NAME(radix_lsd_sort_with)(size_t len, T array[len],
+                                                  unsigned base,
+                                                  unsigned num_of_keys,
+                                                  LinkNode *buffer) {
+  if (len == 0)
+    return SORT_OK;
+
+  // a buffer of len nodes would overflow the C stack for large arrays
+  LinkNode *node_buf = buffer != NULL ? buffer : malloc(len * sizeof(LinkNode));
+  if (node_buf == NULL)
+    return SORT_NO_MEMORY;
+  // copy to linklist
+  LinkList list = NAME(array2linklist)(len, array, node_buf);
 
   // use radix lsd sort the linklist.
-  for (unsigned i = 0, offset = 1; i < num_of_keys; i += 1, offset *= base)
+  uint64_t offset = 1;
+  for (unsigned i = 0; i < num_of_keys; i += 1, offset *= base)
     list = radix_split_and_merge(list, offset, base);
 
-  linklist2array(len, list, array); // copy back to array.
+  NAME(linklist2array)(len, list, array); // copy back to array.
+
+  if (buffer == NULL)
+    free(node_buf);
+  return SORT_OK;
 }
 
 /// Radix LSD Sort
 ///
-/// default base is 256, number of keys is 4
-/// radix_lsd_sort_with(len, array[len], 256, 4);
+/// Sort the keys of the elements (see sort.h) with base 256,
+/// the number of keys is KEY_BYTES, see each-type.h
+/// radix_lsd_sort_with(len, array[len], 256, KEY_BYTES, buffer);
 ///
 /// # Example
 ///
 /// int array[] = {1, 3, 5, 0, 3, 4, 4, 8};
-/// radix_sort(8, array);
+/// radix_lsd_sort_i32(8, array, NULL);
 /// // array = {0, 1, 3, 3, 4, 4, 5, 8}
-void radix_lsd_sort(unsigned len, int array[len]) {
-  // Convert a signed number to an unsigned number
-  // and preserve the size relationship.
-  const unsigned BIAS = UINT_MAX / 2 + 1; // BIAS = 1 * 2^31
-  // Add BIAS to each element.
-  for (unsigned i = 0; i < len; i++)
-    array[i] = (unsigned)array[i] + BIAS;
-
-  // Divide a u32(4 bytes) into 4 u8(1 byte, [0, 256)) subsets and use radix lsd
-  // sort.
-  radix_lsd_sort_with(len, (unsigned *)array, 256, 4);
-
-  // Restore the original value.
-  for (unsigned i = 0; i < len; i++)
-    array[i] = (int)(array[i] - BIAS);
+SortError NAME(radix_lsd_sort)(size_t len, T array[len], LinkNode *buffer) {
+  // Divide the key into KEY_BYTES u8(1 byte, [0, 256)) subsets
+  // and use radix lsd sort.
+  return NAME(radix_lsd_sort_with)(len, array, 256, KEY_BYTES, buffer);
 }
 
-/* #define TEST */
-#ifdef TEST
-
-#include "test-utils.c"
-
-void test_bias() {
-  int array[] = {-1, 8, 19, -3, 996, INT_MIN, INT_MAX, 0, 1, 2, 3, 4, 5, 6, 7};
-  radix_lsd_sort(ARRAY_LEN(array), array);
-  DISPLAY_ARRAY(array);
-}
-
-int main(int argc, char *argv[]) {
-  test_bias();
-  return EXIT_SUCCESS;
-}
-
-#endif /* ifdef TEST */
+#endif /* TEMPLATE */
@@ -1,28 +1,59 @@
+#ifndef TEMPLATE
 #include "sort.h"
 
 typedef struct MinMaxIndex {
-  unsigned min;
-  unsigned max;
+  size_t min;
+  size_t max;
 } MinMaxIndex;
 
-static inline MinMaxIndex select_maxmin_index(unsigned len, int array[len]) {
+#define TEMPLATE "selection-sort.c"
+#include "each-type.h"
+
+/* #define TEST */
+#ifdef TEST
+
+#include "test-utils.c"
+
+void test_empty() {
+  int array[] = {};
+  selection_sort_i32(0, array);
+}
+
+void test_sort() {
+  /* int random[] = {1, 3, 5, 0, 3, -1, 4, 8}; */
+  int random[] = {3, 1, 2};
+  DISPLAY_ARRAY(random);
+  selection_sort_i32(ARRAY_LEN(random), random);
+  DISPLAY_ARRAY(random);
+}
+
+int main() {
+  test_empty();
+  test_sort();
+}
+
+#endif /* ifdef TEST */
+
+#else /* TEMPLATE */
+
+static inline MinMaxIndex NAME(select_maxmin_index)(size_t len, T array[len]) {
   MinMaxIndex minmax = {0, 0};
 
-  for (unsigned i = 1; i < len; i++) {
-    if (array[i] < array[minmax.min])
+  for (size_t i = 1; i < len; i++) {
+    if (LESS(array[i], array[minmax.min]))
       minmax.min = i;
-    else if (array[i] > array[minmax.max])
+    else if (LESS(array[minmax.max], array[i]))
       minmax.max = i;
   }
 
   return minmax;
 }
 
-void selection_sort(unsigned len, int array[len]) {
+void NAME(selection_sort)(size_t len, T array[len]) {
   if (len < 2)
     return;
 
-  MinMaxIndex minmax = select_maxmin_index(len, array);
+  MinMaxIndex minmax = NAME(select_maxmin_index)(len, array);
 
   /* Error: Read the modified area
     // [4, 3, 3, 1] --error-> [4, 3, 3, 1]
@@ -33,10 +64,10 @@ void selection_sort(unsigned len, int array[len]) {
   /* Error: Double write.
    * [3, 1, 2] --error-> [1, 3, 3]
     // copy
-    int max = array[minmax.max];
-    int min = array[minmax.min];
-    int head = array[0];
-    int tail = array[len - 1];
+    T max = array[minmax.max];
+    T min = array[minmax.min];
+    T head = array[0];
+    T tail = array[len - 1];
     // write
     array[minmax.min] = head;
     array[minmax.max] = tail;
@@ -52,30 +83,7 @@ void selection_sort(unsigned len, int array[len]) {
     SWAP(array[minmax.max], array[len - 1]);
   }
 
-  selection_sort(len - 2, &array[1]);
-}
-
-/* #define TEST */
-#ifdef TEST
-
-#include "test-utils.c"
-
-void test_empty() {
-  int array[] = {};
-  selection_sort(0, array);
+  NAME(selection_sort)(len - 2, &array[1]);
 }
 
-void test_sort() {
-  /* int random[] = {1, 3, 5, 0, 3, -1, 4, 8}; */
-  int random[] = {3, 1, 2};
-  DISPLAY_ARRAY(random);
-  selection_sort(ARRAY_LEN(random), random);
-  DISPLAY_ARRAY(random);
-}
-
-int main() {
-  test_empty();
-  test_sort();
-}
-
-#endif /* ifdef TEST */
+#endif /* TEMPLATE */
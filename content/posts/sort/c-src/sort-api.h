@@ -0,0 +1,85 @@
+// Declarations of the sorts for the element type T, see each-type.h
+//
+// Floats are sorted in total order, see FLOAT_ORDER in sort.h.
+
+/// Merge two sorted array into one sorted array
+///
+/// `buffer` is scratch space of at least `len` elements,
+/// or NULL to allocate it on the heap.
+///
+/// Return `SORT_BAD_SPLIT` if `split > len`.
+///
+/// # Example
+///
+/// int array[] = {1, 3, 5, 0, 3, 4, 4, 8};
+/// //                     ^split        ^len
+/// merge_two_sorted_array_i32(8, array, 3, NULL);
+/// // array = {0, 1, 3, 3, 4, 4, 5, 8}
+SortError NAME(merge_two_sorted_array)(size_t len, T array[len], size_t split,
+                                       T *buffer);
+
+/// Bubble sort
+///
+/// # Example
+///
+/// int array[] = {1, 3, 5, 0, 3, 4, 4, 8};
+/// bubble_sort_i32(8, array);
+/// // array = {0, 1, 3, 3, 4, 4, 5, 8}
+void NAME(bubble_sort)(size_t len, T array[len]);
+
+/// Selection sort
+///
+/// # Example
+///
+/// int array[] = {1, 3, 5, 0, 3, 4, 4, 8};
+/// selection_sort_i32(8, array);
+/// // array = {0, 1, 3, 3, 4, 4, 5, 8}
+void NAME(selection_sort)(size_t len, T array[len]);
+
+/// Insertion sort
+///
+/// # Example
+///
+/// int array[] = {1, 3, 5, 0, 3, 4, 4, 8};
+/// insertion_sort_i32(8, array);
+/// // array = {0, 1, 3, 3, 4, 4, 5, 8}
+void NAME(insertion_sort)(size_t len, T array[len]);
+
+/// Merge sort
+///
+/// `buffer` is scratch space of at least `len` elements,
+/// or NULL to allocate it on the heap.
+///
+/// # Example
+///
+/// int array[] = {1, 3, 5, 0, 3, 4, 4, 8};
+/// merge_sort_i32(8, array, NULL);
+/// // array = {0, 1, 3, 3, 4, 4, 5, 8}
+SortError NAME(merge_sort)(size_t len, T array[len], T *buffer);
+
+/// Merge sort parallel.
+///
+/// `buffer` is as for `merge_sort`.
+SortError NAME(merge_sort_parallel)(size_t len, T array[len], T *buffer);
+
+/// c std qsort
+void NAME(std_qsort)(size_t len, T array[len]);
+
+/// Quick sort
+void NAME(quick_sort)(size_t len, T array[len]);
+
+/// Radix LSD Sort
+///
+/// Sort the keys of the elements (see sort.h) with base 256,
+/// the number of keys is KEY_BYTES, see each-type.h
+/// radix_lsd_sort_with(len, keys[len], 256, KEY_BYTES, buffer);
+///
+/// `buffer` is scratch space of at least `len` nodes,
+/// or NULL to allocate it on the heap.
+///
+/// # Example
+///
+/// int array[] = {1, 3, 5, 0, 3, 4, 4, 8};
+/// radix_lsd_sort_i32(8, array, NULL);
+/// // array = {0, 1, 3, 3, 4, 4, 5, 8}
+SortError NAME(radix_lsd_sort)(size_t len, T array[len], LinkNode *buffer);
@@ -1,73 +1,141 @@
+#ifndef SORT_H
+#define SORT_H
+
+#include <stdatomic.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <string.h>
+
+// T is the element type of the template, see each-type.h
 #define SWAP(x, y)                                                             \
   {                                                                            \
-    int tmp = x;                                                               \
+    T tmp = x;                                                                 \
     x = y;                                                                     \
     y = tmp;                                                                   \
+    SWAPPED();                                                                 \
+  }
+
+/// Result of the sorts which can fail.
+typedef enum SortError {
+  SORT_OK = 0,
+  /// `split` is greater than `len`.
+  SORT_BAD_SPLIT = 1,
+  /// The scratch buffer could not be allocated.
+  SORT_NO_MEMORY = 2,
+} SortError;
+
+/// Node of the linked lists used by radix sort.
+typedef struct LinkNode {
+  uint64_t data;
+  struct LinkNode *next;
+} LinkNode;
+
+// key_SUFFIX maps an element to an unsigned key of the same size with the
+// same order, from_key_SUFFIX maps it back and less_SUFFIX compares elements.
+
+#define UNSIGNED_ORDER(SUFFIX, T)                                              \
+  static inline T key_##SUFFIX(T x) { return x; }                              \
+  static inline T from_key_##SUFFIX(T key) { return key; }                     \
+  static inline bool less_##SUFFIX(T a, T b) { return a < b; }
+
+// flip the sign bit
+#define SIGNED_ORDER(SUFFIX, T, U)                                             \
+  static inline U key_##SUFFIX(T x) {                                          \
+    return (U)x ^ (U)1 << (sizeof(U) * 8 - 1);                                 \
+  }                                                                            \
+  static inline T from_key_##SUFFIX(U key) {                                   \
+    return (T)(key ^ (U)1 << (sizeof(U) * 8 - 1));                             \
+  }                                                                            \
+  static inline bool less_##SUFFIX(T a, T b) { return a < b; }
+
+// Total order of IEEE 754 floats:
+// -NaN < -inf < ... < -0.0 < 0.0 < ... < inf < NaN
+// flip every bit of negative numbers and the sign bit of the others
+#define FLOAT_ORDER(SUFFIX, T, U)                                              \
+  static inline U key_##SUFFIX(T x) {                                          \
+    const U SIGN = (U)1 << (sizeof(U) * 8 - 1);                                \
+    U bits;                                                                    \
+    memcpy(&bits, &x, sizeof(bits));                                           \
+    return bits & SIGN ? ~bits : bits | SIGN;                                  \
+  }                                                                            \
+  static inline T from_key_##SUFFIX(U key) {                                   \
+    const U SIGN = (U)1 << (sizeof(U) * 8 - 1);                                \
+    U bits = key & SIGN ? key & ~SIGN : ~key;                                  \
+    T x;                                                                       \
+    memcpy(&x, &bits, sizeof(x));                                              \
+    return x;                                                                  \
+  }                                                                            \
+  static inline bool less_##SUFFIX(T a, T b) {                                 \
+    return key_##SUFFIX(a) < key_##SUFFIX(b);                                  \
   }
 
-/// Merge two sorted array into one sorted array
-///
-/// # Example
-///
-/// int array[] = {1, 3, 5, 0, 3, 4, 4, 8};
-/// //                     ^split        ^len
-/// merge_two_sorted_array(8, array, 3);
-/// // array = {0, 1, 3, 3, 4, 4, 5, 8}
-void merge_two_sorted_array(unsigned len, int array[len], unsigned split);
-
-/// Bubble sort
-///
-/// # Example
-///
-/// int array[] = {1, 3, 5, 0, 3, 4, 4, 8};
-/// bubble_sort(8, array);
-/// // array = {0, 1, 3, 3, 4, 4, 5, 8}
-void bubble_sort(unsigned len, int array[len]);
-
-/// Selection sort
-///
-/// # Example
-///
-/// int array[] = {1, 3, 5, 0, 3, 4, 4, 8};
-/// selection_sort(8, array);
-/// // array = {0, 1, 3, 3, 4, 4, 5, 8}
-void selection_sort(unsigned len, int array[len]);
-
-/// Insertion sort
-///
-/// # Example
-///
-/// int array[] = {1, 3, 5, 0, 3, 4, 4, 8};
-/// insertion_sort(8, array);
-/// // array = {0, 1, 3, 3, 4, 4, 5, 8}
-void insertion_sort(unsigned len, int array[len]);
-
-/// Merge sort
-///
-/// # Example
-///
-/// int array[] = {1, 3, 5, 0, 3, 4, 4, 8};
-/// merge_sort(8, array);
-/// // array = {0, 1, 3, 3, 4, 4, 5, 8}
-void merge_sort(unsigned len, int array[len]);
-
-/// Merge sort parallel.
-void merge_sort_parallel(unsigned len, int array[len]);
-
-/// c std qsort
-void std_qsort(unsigned len, int array[len]);
-
-/// Quick sort
-void quick_sort(unsigned len, int array[len]);
-
-/// Radix LSD Sort
-///
-/// default base is 256, number of keys is 4
-/// radix_lsd_sort_with(len, array[len], 256, 4);
-///
-/// # Example
-///
-/// int array[] = {1, 3, 5, 0, 3, 4, 4, 8};
-/// radix_sort(8, array);
-/// // array = {0, 1, 3, 3, 4, 4, 5, 8}
-void radix_lsd_sort(unsigned len, int array[len]);
+SIGNED_ORDER(i32, int32_t, uint32_t)
+SIGNED_ORDER(i64, int64_t, uint64_t)
+UNSIGNED_ORDER(u32, uint32_t)
+UNSIGNED_ORDER(u64, uint64_t)
+FLOAT_ORDER(f32, float, uint32_t)
+FLOAT_ORDER(f64, double, uint64_t)
+
+// wrote_SUFFIX(n) is called after n elements are stored to the array or a
+// buffer outside of SWAP, swapped_SUFFIX after every SWAP.
+// Only the counted type counts them.
+#define UNCOUNTED(SUFFIX)                                                      \
+  static inline void wrote_##SUFFIX(size_t n) { (void)n; }                     \
+  static inline void swapped_##SUFFIX(void) {}
+
+UNCOUNTED(i32)
+UNCOUNTED(i64)
+UNCOUNTED(u32)
+UNCOUNTED(u64)
+UNCOUNTED(f32)
+UNCOUNTED(f64)
+UNCOUNTED(keyed)
+
+// u64 ordered only by its high half, the low half is a payload.
+// Equal elements can be told apart by their payload, which shows whether a
+// sort is stable. The key rotates the high half to the bytes radix sort reads.
+static inline uint64_t key_keyed(uint64_t x) { return x >> 32 | x << 32; }
+static inline uint64_t from_key_keyed(uint64_t key) {
+  return key >> 32 | key << 32;
+}
+static inline bool less_keyed(uint64_t a, uint64_t b) {
+  return a >> 32 < b >> 32;
+}
+
+// keyed, counting every comparison, write and swap, see counted.c
+extern atomic_size_t counted_comparisons;
+extern atomic_size_t counted_writes;
+extern atomic_size_t counted_swaps;
+static inline uint64_t key_counted(uint64_t x) { return key_keyed(x); }
+static inline uint64_t from_key_counted(uint64_t key) {
+  return from_key_keyed(key);
+}
+static inline bool less_counted(uint64_t a, uint64_t b) {
+  atomic_fetch_add_explicit(&counted_comparisons, 1, memory_order_relaxed);
+  return less_keyed(a, b);
+}
+static inline void wrote_counted(size_t n) {
+  atomic_fetch_add_explicit(&counted_writes, n, memory_order_relaxed);
+}
+// a swap stores two elements
+static inline void swapped_counted(void) {
+  atomic_fetch_add_explicit(&counted_swaps, 1, memory_order_relaxed);
+  wrote_counted(2);
+}
+
+/// Return the number of comparisons made by the sorts of the counted type,
+/// and start counting again from 0.
+size_t take_comparisons(void);
+/// Return the number of elements stored by the sorts of the counted type,
+/// two for every swap, and start counting again from 0.
+size_t take_writes(void);
+/// Return the number of swaps made by the sorts of the counted type,
+/// and start counting again from 0.
+size_t take_swaps(void);
+
+// declare every sort for every element type
+#define TEMPLATE "sort-api.h"
+#include "each-type.h"
+
+#endif /* SORT_H */
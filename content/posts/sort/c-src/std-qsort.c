@@ -1,7 +1,20 @@
+#ifndef TEMPLATE
+#include "sort.h"
 #include <stdlib.h>
 
-int compare(const void *a, const void *b) { return *(int *)a - *(int *)b; }
+#define TEMPLATE "std-qsort.c"
+#include "each-type.h"
 
-void std_qsort(unsigned len, int array[len]) {
-  qsort(array, len, sizeof(int), compare);
+#else /* TEMPLATE */
+
+static int NAME(compare)(const void *a, const void *b) {
+  T x = *(const T *)a;
+  T y = *(const T *)b;
+  return LESS(y, x) - LESS(x, y);
+}
+
+void NAME(std_qsort)(size_t len, T array[len]) {
+  qsort(array, len, sizeof(T), NAME(compare));
 }
+
+#endif /* TEMPLATE */
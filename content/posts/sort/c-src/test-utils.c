@@ -1,13 +1,13 @@
 #include <stdio.h>
 
-void display_array(unsigned len, int array[len]) {
+void display_array(size_t len, int array[len]) {
   if (len == 0) {
     printf("[]\n");
     return;
   }
 
   putchar('[');
-  for (unsigned i = 0; i < len - 1; i++)
+  for (size_t i = 0; i < len - 1; i++)
     printf("%d, ", array[i]);
   printf("%d]\n", array[len - 1]);
 }
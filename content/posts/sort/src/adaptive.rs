@@ -0,0 +1,529 @@
+//! Pick the algorithm from the shape of the input.
+//!
+//! A few hundred comparisons on evenly spaced samples estimate
+//! how presorted the input is, then one of insertion, natural merge,
+//! radix or quick sort is used. [`Adaptive::decide`] returns the
+//! [`Decision`] that `sort` makes, so it can be audited.
+//!
+//! Like the C sorts, it sorts `i32`. Radix sort only knows the natural order,
+//! so it is only chosen by `sort` / `sort_slice`, never by `sort_method`.
+//!
+//! # Examples
+//!
+//! ```
+//! use sort::adaptive::{Adaptive, Strategy};
+//! use sort::SliceSolution;
+//! let mut vec: Vec<i32> = (0..10_000).collect();
+//! vec.swap(10, 20);
+//! assert_eq!(Adaptive::decide(&vec).strategy, Strategy::Insertion);
+//! assert!(Adaptive::sort_slice(&mut vec).force_check());
+//! ```
+
+use crate::merge_two_sorted::merge_iter_by;
+use crate::select::{insertion_sort, quick_sort};
+use crate::{SliceSolution, Solution, Sorted, SortedSlice};
+use std::fmt;
+
+/// Inputs not longer than this are always sorted by insertion.
+const SMALL: usize = 32;
+
+/// Elements sampled for the inversion, distinct and range estimates.
+const SAMPLE: usize = 64;
+
+/// Adjacent pairs sampled for the run estimate.
+const PAIRS: usize = 256;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Strategy {
+    /// Few inversions and few runs, each element moves only a short distance.
+    ///
+    /// Gives up for natural merge after `2 * len` moves,
+    /// in case the samples missed the disorder.
+    Insertion,
+    /// Few long ascending runs.
+    NaturalMerge,
+    /// Long input in natural order, or values within 16 bits.
+    Radix,
+    /// Anything else, with a three-way partition for repeated values.
+    Quick,
+}
+
+/// Estimates from samples of the input, not exact counts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Profile {
+    pub len: usize,
+    /// Ascending runs.
+    pub runs: usize,
+    /// Pairs in the wrong order.
+    pub inversions: usize,
+    /// Distinct values.
+    pub distinct: usize,
+    /// Difference between the greatest and the least value.
+    pub range: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Decision {
+    pub profile: Profile,
+    pub strategy: Strategy,
+}
+
+impl fmt::Display for Decision {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let p = &self.profile;
+        write!(
+            f,
+            "{:?} for {} elements (~{} runs, ~{} inversions, ~{} distinct, range ~{})",
+            self.strategy, p.len, p.runs, p.inversions, p.distinct, p.range
+        )
+    }
+}
+
+#[derive(Copy, Clone)]
+pub struct Adaptive;
+
+impl Adaptive {
+    /// The decision of `sort` and `sort_slice` on `slice`.
+    pub fn decide(slice: &[i32]) -> Decision {
+        decide(slice, &mut i32::lt, true)
+    }
+
+    /// The decision of `sort_method` and `sort_slice_method` on `slice`.
+    pub fn decide_by<F>(slice: &[i32], is_less: F) -> Decision
+    where
+        F: FnMut(&i32, &i32) -> bool,
+    {
+        let mut is_less = is_less;
+        decide(slice, &mut is_less, false)
+    }
+}
+
+impl<const N: usize> Solution<i32, N> for Adaptive {
+    fn sort_method<F>(array: [i32; N], is_less: F) -> Sorted<i32, N, impl FnMut(&i32, &i32) -> bool>
+    where
+        F: FnMut(&i32, &i32) -> bool,
+    {
+        let mut is_less = is_less;
+        let mut array = array;
+        let decision = decide(&array, &mut is_less, false);
+        run(decision.strategy, &mut array, &mut is_less);
+        unsafe { Sorted::uncheck_from_array(array, is_less) }
+    }
+
+    fn sort(array: [i32; N]) -> Sorted<i32, N, impl FnMut(&i32, &i32) -> bool> {
+        let mut array = array;
+        run(Adaptive::decide(&array).strategy, &mut array, &mut i32::lt);
+        unsafe { Sorted::uncheck_from_array(array, i32::lt) }
+    }
+}
+
+impl SliceSolution<i32> for Adaptive {
+    fn sort_slice_method<F>(
+        slice: &mut [i32],
+        is_less: F,
+    ) -> SortedSlice<'_, i32, impl FnMut(&i32, &i32) -> bool>
+    where
+        F: FnMut(&i32, &i32) -> bool,
+    {
+        let mut is_less = is_less;
+        let decision = decide(slice, &mut is_less, false);
+        run(decision.strategy, slice, &mut is_less);
+        unsafe { SortedSlice::uncheck_from_slice(slice, is_less) }
+    }
+
+    fn sort_slice(slice: &mut [i32]) -> SortedSlice<'_, i32, impl FnMut(&i32, &i32) -> bool> {
+        run(Adaptive::decide(slice).strategy, slice, &mut i32::lt);
+        unsafe { SortedSlice::uncheck_from_slice(slice, i32::lt) }
+    }
+}
+
+fn profile<F>(slice: &[i32], is_less: &mut F) -> Profile
+where
+    F: FnMut(&i32, &i32) -> bool,
+{
+    let len = slice.len();
+    if len < 2 {
+        return Profile {
+            len,
+            runs: len,
+            inversions: 0,
+            distinct: len,
+            range: 0,
+        };
+    }
+
+    // every descent between neighbours starts a new run
+    let pairs = PAIRS.min(len - 1);
+    let descents = (0..pairs)
+        .map(|k| k * (len - 1) / pairs)
+        .filter(|&i| is_less(&slice[i + 1], &slice[i]))
+        .count();
+    let runs = 1 + descents * (len - 1) / pairs;
+
+    let sample: Vec<i32> = (0..SAMPLE.min(len))
+        .map(|k| slice[k * len / SAMPLE.min(len)])
+        .collect();
+    let k = sample.len();
+
+    let mut sample_inversions = 0;
+    for i in 0..k {
+        for j in i + 1..k {
+            if is_less(&sample[j], &sample[i]) {
+                sample_inversions += 1;
+            }
+        }
+    }
+    let all_pairs = len as u128 * (len as u128 - 1) / 2;
+    let sample_pairs = k as u128 * (k as u128 - 1) / 2;
+    let inversions = (sample_inversions as u128 * all_pairs / sample_pairs) as usize;
+
+    let mut sorted = sample.clone();
+    insertion_sort(&mut sorted, is_less);
+    let sample_distinct = 1 + sorted.windows(2).filter(|w| is_less(&w[0], &w[1])).count();
+    // values repeated in the sample are likely all the values there are
+    let distinct = if sample_distinct * 2 <= k {
+        sample_distinct
+    } else {
+        sample_distinct * len / k
+    };
+
+    let min = *sample.iter().min().unwrap() as i64;
+    let max = *sample.iter().max().unwrap() as i64;
+
+    Profile {
+        len,
+        runs,
+        inversions,
+        distinct,
+        range: (max - min) as u64,
+    }
+}
+
+fn decide<F>(slice: &[i32], is_less: &mut F, natural: bool) -> Decision
+where
+    F: FnMut(&i32, &i32) -> bool,
+{
+    let profile = profile(slice, is_less);
+    let len = profile.len;
+
+    // the samples for inversions are far apart and miss local disorder,
+    // which the runs of neighbouring pairs reveal
+    let strategy = if len <= SMALL || (profile.inversions <= len && profile.runs <= len / 16) {
+        Strategy::Insertion
+    } else if profile.runs <= len / 16 {
+        Strategy::NaturalMerge
+    } else if profile.distinct <= 16 {
+        // a three-way partition removes a value at every level
+        Strategy::Quick
+    } else if natural && len >= 256 && (len >= 4096 || profile.range < 1 << 16) {
+        Strategy::Radix
+    } else {
+        Strategy::Quick
+    };
+
+    Decision { profile, strategy }
+}
+
+fn run<F>(strategy: Strategy, slice: &mut [i32], is_less: &mut F)
+where
+    F: FnMut(&i32, &i32) -> bool,
+{
+    match strategy {
+        Strategy::Insertion => {
+            if !bounded_insertion_sort(slice, is_less, 2 * slice.len()) {
+                natural_merge_sort(slice, is_less);
+            }
+        }
+        Strategy::NaturalMerge => natural_merge_sort(slice, is_less),
+        Strategy::Radix => radix_sort(slice),
+        Strategy::Quick => quick_sort(slice, is_less),
+    }
+}
+
+/// Insertion sort stopping after `limit` moves, returns whether it finished.
+fn bounded_insertion_sort<F>(slice: &mut [i32], is_less: &mut F, limit: usize) -> bool
+where
+    F: FnMut(&i32, &i32) -> bool,
+{
+    let mut moves = 0;
+    for i in 1..slice.len() {
+        let mut j = i;
+        while j > 0 && is_less(&slice[j], &slice[j - 1]) {
+            if moves == limit {
+                return false;
+            }
+            slice.swap(j - 1, j);
+            j -= 1;
+            moves += 1;
+        }
+    }
+    true
+}
+
+/// Merge neighbouring ascending runs until one is left.
+fn natural_merge_sort<F>(slice: &mut [i32], is_less: &mut F)
+where
+    F: FnMut(&i32, &i32) -> bool,
+{
+    // end of every run
+    let mut ends: Vec<usize> = (1..slice.len())
+        .filter(|&i| is_less(&slice[i], &slice[i - 1]))
+        .chain([slice.len()])
+        .collect();
+
+    let mut buffer = Vec::with_capacity(slice.len());
+    while ends.len() > 1 {
+        let mut start = 0;
+        for pair in ends.chunks(2) {
+            if let [mid, end] = *pair {
+                buffer.clear();
+                buffer.extend(merge_iter_by(
+                    slice[start..mid].iter().copied(),
+                    slice[mid..end].iter().copied(),
+                    &mut *is_less,
+                ));
+                slice[start..end].copy_from_slice(&buffer);
+            }
+            start = *pair.last().unwrap();
+        }
+        ends = ends.chunks(2).map(|pair| *pair.last().unwrap()).collect();
+    }
+}
+
+/// LSD radix sort on bytes of `x - min`, skipping the bytes that are zero
+/// for every element.
+fn radix_sort(slice: &mut [i32]) {
+    let (Some(&min), Some(&max)) = (slice.iter().min(), slice.iter().max()) else {
+        return;
+    };
+    let key = |x: i32| (x as i64 - min as i64) as u32;
+    let bytes = (32 - key(max).leading_zeros()).div_ceil(8);
+
+    let mut buffer = vec![0; slice.len()];
+    for byte in 0..bytes {
+        let digit = |x: i32| (key(x) >> (byte * 8)) as usize & 0xff;
+
+        let mut offsets = [0; 257];
+        for &x in slice.iter() {
+            offsets[digit(x) + 1] += 1;
+        }
+        for i in 1..257 {
+            offsets[i] += offsets[i - 1];
+        }
+
+        for &x in slice.iter() {
+            buffer[offsets[digit(x)]] = x;
+            offsets[digit(x)] += 1;
+        }
+        slice.copy_from_slice(&buffer);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::*;
+
+    const STRATEGIES: [Strategy; 4] = [
+        Strategy::Insertion,
+        Strategy::NaturalMerge,
+        Strategy::Radix,
+        Strategy::Quick,
+    ];
+
+    #[test]
+    fn simple() {
+        let array = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9];
+        let a = Adaptive::sort_method(array, |a, b| a < b);
+        assert!(a.force_check());
+        assert_eq!(a.take(), [0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+    }
+
+    #[test]
+    fn empty() {
+        test_empty(Adaptive);
+    }
+
+    #[test]
+    fn random() {
+        test_random(Adaptive);
+    }
+
+    #[test]
+    fn stroll() {
+        test_stroll(Adaptive);
+    }
+
+    #[test]
+    fn trend_increasing() {
+        test_trend_increasing(Adaptive);
+    }
+
+    #[test]
+    fn gaussian_with_noise() {
+        test_gaussian_with_noise(Adaptive);
+    }
+
+    #[test]
+    fn low_sample_sin_with_noise() {
+        test_low_sample_sin_with_noise(Adaptive);
+    }
+
+    #[test]
+    fn high_sample_sin_with_noise() {
+        test_high_sample_sin_with_noise(Adaptive);
+    }
+
+    #[test]
+    fn every_strategy_sorts() {
+        for (name, data) in bench_data() {
+            let mut expected = data.clone();
+            expected.sort();
+            for strategy in STRATEGIES {
+                let mut vec = data.clone();
+                run(strategy, &mut vec, &mut i32::lt);
+                assert_eq!(vec, expected, "{:?} on {}", strategy, name);
+            }
+
+            let mut vec = data.clone();
+            natural_merge_sort(&mut vec, &mut i32::gt);
+            expected.reverse();
+            assert_eq!(vec, expected, "reversed natural merge on {}", name);
+        }
+
+        let mut vec = vec![i32::MAX, 0, i32::MIN, -1, 1];
+        radix_sort(&mut vec);
+        assert_eq!(vec, [i32::MIN, -1, 0, 1, i32::MAX]);
+    }
+
+    #[test]
+    fn decisions() {
+        let decide = |vec: &[i32]| Adaptive::decide(vec).strategy;
+
+        assert_eq!(decide(&[3, 1, 2]), Strategy::Insertion);
+
+        let mut nearly_sorted: Vec<i32> = (0..10_000).collect();
+        nearly_sorted.swap(100, 101);
+        assert_eq!(decide(&nearly_sorted), Strategy::Insertion);
+
+        let runs: Vec<i32> = (0..8).flat_map(|_| (0..1000).map(|x| x * 7)).collect();
+        assert_eq!(decide(&runs), Strategy::NaturalMerge);
+
+        let few_values: Vec<i32> = (0..10_000).map(|x| (x * 7919) % 5 * 1_000_000).collect();
+        assert_eq!(decide(&few_values), Strategy::Quick);
+
+        for (name, data) in bench_data() {
+            let decision = Adaptive::decide(&data);
+            assert_eq!(decision.strategy, Strategy::Radix, "{}: {}", name, decision);
+            // a custom order can not use radix sort
+            let decision = Adaptive::decide_by(&data, |a, b| a < b);
+            assert_eq!(decision.strategy, Strategy::Quick, "{}: {}", name, decision);
+        }
+    }
+
+    /// Samples of every block are ascending, but almost every neighbour descends.
+    #[test]
+    fn block_reversed() {
+        const LEN: i32 = 1 << 20;
+        const BLOCK: i32 = LEN / 64;
+        let mut vec: Vec<i32> = (0..64)
+            .flat_map(|block| (0..BLOCK).rev().map(move |x| block * BLOCK + x))
+            .collect();
+
+        let decision = Adaptive::decide(&vec);
+        assert_eq!(decision.profile.inversions, 0);
+        assert_ne!(decision.strategy, Strategy::Insertion, "{}", decision);
+        let decision = Adaptive::decide_by(&vec, |a, b| a < b);
+        assert_ne!(decision.strategy, Strategy::Insertion, "{}", decision);
+
+        assert!(Adaptive::sort_slice(&mut vec).force_check());
+        assert!(vec.into_iter().eq(0..LEN));
+    }
+
+    /// Sorted at every probe of `profile`, reversed between them.
+    #[test]
+    fn probes_sorted() {
+        const LEN: usize = 1 << 16;
+        let mut probed = vec![false; LEN];
+        for k in 0..PAIRS {
+            let i = k * (LEN - 1) / PAIRS;
+            probed[i] = true;
+            probed[i + 1] = true;
+        }
+        for k in 0..SAMPLE {
+            probed[k * LEN / SAMPLE] = true;
+        }
+        let mut vec: Vec<i32> = (0..LEN as i32).collect();
+        let between: Vec<usize> = (0..LEN).filter(|&i| !probed[i]).collect();
+        for (&i, &j) in between.iter().zip(between.iter().rev()) {
+            if i < j {
+                vec.swap(i, j);
+            }
+        }
+
+        let decision = Adaptive::decide(&vec);
+        assert_eq!((decision.profile.runs, decision.profile.inversions), (1, 0));
+        assert_eq!(decision.strategy, Strategy::Insertion, "{}", decision);
+
+        let mut negated: Vec<i32> = vec.iter().map(|x| -x).collect();
+        let decision = Adaptive::decide_by(&negated, |a, b| a > b);
+        assert_eq!(decision.strategy, Strategy::Insertion, "{}", decision);
+
+        assert!(Adaptive::sort_slice(&mut vec).force_check());
+        assert!(vec.into_iter().eq(0..LEN as i32));
+        assert!(Adaptive::sort_slice_method(&mut negated, |a, b| a > b).force_check());
+        assert!(negated.into_iter().eq((0..LEN as i32).map(|x| -x)));
+    }
+
+    #[test]
+    fn profile_estimates() {
+        let sorted: Vec<i32> = (0..10_000).collect();
+        let p = Adaptive::decide(&sorted).profile;
+        assert_eq!((p.runs, p.inversions, p.distinct), (1, 0, 10_000));
+
+        let reversed: Vec<i32> = (0..10_000).rev().collect();
+        let p = Adaptive::decide(&reversed).profile;
+        assert_eq!(p.runs, 10_000);
+        assert_eq!(p.inversions, 10_000 * 9_999 / 2);
+        assert!(p.range > 9_000);
+    }
+
+    mod slice {
+        use super::*;
+
+        #[test]
+        fn empty() {
+            test_slice_empty(Adaptive);
+        }
+
+        #[test]
+        fn random() {
+            test_slice_random(Adaptive);
+        }
+
+        #[test]
+        fn stroll() {
+            test_slice_stroll(Adaptive);
+        }
+
+        #[test]
+        fn trend_increasing() {
+            test_slice_trend_increasing(Adaptive);
+        }
+
+        #[test]
+        fn gaussian_with_noise() {
+            test_slice_gaussian_with_noise(Adaptive);
+        }
+
+        #[test]
+        fn low_sample_sin_with_noise() {
+            test_slice_low_sample_sin_with_noise(Adaptive);
+        }
+
+        #[test]
+        fn high_sample_sin_with_noise() {
+            test_slice_high_sample_sin_with_noise(Adaptive);
+        }
+    }
+}
@@ -0,0 +1,31 @@
+use serde::Serialize;
+use sort::metrics::Disorder;
+use sort::test_data::BENCH_DATA;
+
+#[derive(Serialize)]
+struct Report {
+    data: &'static str,
+    disorder: Disorder,
+}
+
+/// Measure the disorder of every file in `bench-data`.
+///
+/// ```sh
+/// cargo run --bin=sort-metrics > sort-metrics.json
+/// ```
+fn main() {
+    let reports: Vec<_> = BENCH_DATA
+        .into_iter()
+        .map(|data| {
+            let path = format!("./bench-data/{}.json", data);
+            let json = std::fs::read_to_string(&path).unwrap();
+            let array: Vec<i32> = serde_json::from_str(&json).unwrap();
+            Report {
+                data,
+                disorder: Disorder::of(&array),
+            }
+        })
+        .collect();
+
+    println!("{}", serde_json::to_string_pretty(&reports).unwrap());
+}
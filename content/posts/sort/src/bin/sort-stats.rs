@@ -0,0 +1,38 @@
+use serde::Serialize;
+use sort::instrument::SortStats;
+use sort::registry::ALGORITHMS;
+use sort::test_data::BENCH_DATA;
+
+#[derive(Serialize)]
+struct Report {
+    algorithm: &'static str,
+    data: &'static str,
+    stats: SortStats,
+}
+
+/// Count the work of every instrumented algorithm on every file in `bench-data`.
+///
+/// ```sh
+/// cargo run --bin=sort-stats > sort-stats.json
+/// ```
+fn main() {
+    let mut reports = Vec::new();
+
+    for data in BENCH_DATA {
+        let path = format!("./bench-data/{}.json", data);
+        let json = std::fs::read_to_string(&path).unwrap();
+        let array: Vec<i32> = serde_json::from_str(&json).unwrap();
+
+        for algorithm in ALGORITHMS {
+            if let Some(measure) = algorithm.measure {
+                reports.push(Report {
+                    algorithm: algorithm.name,
+                    data,
+                    stats: measure(&array),
+                });
+            }
+        }
+    }
+
+    println!("{}", serde_json::to_string_pretty(&reports).unwrap());
+}
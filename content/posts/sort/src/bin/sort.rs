@@ -0,0 +1,39 @@
+use sort::registry;
+use std::io::Read;
+
+/// Sort a JSON array of integers from stdin with an algorithm chosen by name.
+///
+/// ```sh
+/// cargo run --bin=sort -- quick < ./bench-data/random.json
+/// cargo run --bin=sort -- --list
+/// ```
+fn main() {
+    let name = std::env::args().nth(1).unwrap_or_default();
+
+    if name == "--list" {
+        for algorithm in registry::ALGORITHMS {
+            println!(
+                "{:<16} {:<12} stable: {:<5} in place: {:<5} parallel: {}",
+                algorithm.name,
+                algorithm.complexity.to_string(),
+                algorithm.stable,
+                algorithm.in_place,
+                algorithm.parallel
+            );
+        }
+        return;
+    }
+
+    let Some(algorithm) = registry::find(&name) else {
+        let names: Vec<_> = registry::names().collect();
+        eprintln!("unknown algorithm {:?}, expected one of {:?}", name, names);
+        std::process::exit(1);
+    };
+
+    let mut input = String::new();
+    std::io::stdin().read_to_string(&mut input).unwrap();
+    let mut data: Vec<i32> = serde_json::from_str(&input).unwrap();
+
+    (algorithm.sort)(&mut data);
+    println!("{:?}", data);
+}
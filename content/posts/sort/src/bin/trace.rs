@@ -0,0 +1,24 @@
+use sort::trace::{trace, TRACEABLE};
+use std::io::Read;
+
+/// Trace sorting a small JSON array of integers from stdin.
+///
+/// ```sh
+/// echo '[3, 1, 2]' | cargo run --bin=trace -- quick > trace.json
+/// ```
+fn main() {
+    let name = std::env::args().nth(1).unwrap_or_default();
+
+    let mut input = String::new();
+    std::io::stdin().read_to_string(&mut input).unwrap();
+    let data: Vec<i32> = serde_json::from_str(&input).unwrap();
+
+    match trace(&name, &data) {
+        Some(trace) => println!("{}", trace.to_json()),
+        None => {
+            let names = TRACEABLE.map(|(name, _)| name).join(", ");
+            eprintln!("can not trace {:?}, expected one of {}", name, names);
+            std::process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,227 @@
+//! Build `is_less` rules from smaller ones.
+//!
+//! Every function returns an `FnMut(&T, &T) -> bool`, which can be passed to
+//! [`Solution::sort_method`](crate::Solution::sort_method) and is then stored
+//! in [`Sorted`](crate::Sorted) for its queries.
+//!
+//! # Examples
+//!
+//! ```
+//! use sort::compare::{by_key, by_key_desc, then_by};
+//! use sort::{Solution, Stable};
+//! let people = [("bob", 30), ("alice", 25), ("carol", 30)];
+//! let sorted = Stable::sort_method(people, then_by(by_key_desc(|p: &(&str, i32)| p.1), by_key(|p: &(&str, i32)| p.0)));
+//! assert_eq!(*sorted, [("bob", 30), ("carol", 30), ("alice", 25)]);
+//! ```
+
+/// Compare by `key`, ascending.
+pub fn by_key<T, K, F>(key: F) -> impl FnMut(&T, &T) -> bool
+where
+    F: FnMut(&T) -> K,
+    K: PartialOrd,
+{
+    let mut key = key;
+    move |a, b| key(a) < key(b)
+}
+
+/// Compare by `key`, descending.
+pub fn by_key_desc<T, K, F>(key: F) -> impl FnMut(&T, &T) -> bool
+where
+    F: FnMut(&T) -> K,
+    K: PartialOrd,
+{
+    reverse(by_key(key))
+}
+
+/// Compare the keys of `key` with `is_less`,
+/// for keys without `Ord`, such as `Option<f64>`.
+///
+/// # Examples
+///
+/// ```
+/// use sort::compare::{by_key_with, nulls_last, total_order};
+/// use sort::{SliceSolution, Stable};
+/// let mut scores = vec![("a", Some(2.5)), ("b", None), ("c", Some(-1.0))];
+/// Stable::sort_slice_method(&mut scores, by_key_with(|s: &(&str, Option<f64>)| s.1, nulls_last(total_order)));
+/// assert_eq!(scores, [("c", Some(-1.0)), ("a", Some(2.5)), ("b", None)]);
+/// ```
+pub fn by_key_with<T, K, F, G>(key: F, is_less: G) -> impl FnMut(&T, &T) -> bool
+where
+    F: FnMut(&T) -> K,
+    G: FnMut(&K, &K) -> bool,
+{
+    let mut key = key;
+    let mut is_less = is_less;
+    move |a, b| is_less(&key(a), &key(b))
+}
+
+/// The opposite order of `is_less`.
+///
+/// # Examples
+///
+/// ```
+/// use sort::compare::reverse;
+/// use sort::{Solution, Stable};
+/// let sorted = Stable::sort_method([1, 3, 2], reverse(i32::lt));
+/// assert_eq!(*sorted, [3, 2, 1]);
+/// assert_eq!(sorted.lower_bound(&2), 1);
+/// ```
+pub fn reverse<T, F>(is_less: F) -> impl FnMut(&T, &T) -> bool
+where
+    F: FnMut(&T, &T) -> bool,
+{
+    let mut is_less = is_less;
+    move |a, b| is_less(b, a)
+}
+
+/// Order by `first`, and by `second` between elements equal under `first`.
+pub fn then_by<T, F, G>(first: F, second: G) -> impl FnMut(&T, &T) -> bool
+where
+    F: FnMut(&T, &T) -> bool,
+    G: FnMut(&T, &T) -> bool,
+{
+    let mut first = first;
+    let mut second = second;
+    move |a, b| first(a, b) || (!first(b, a) && second(a, b))
+}
+
+/// Order by `first`, and by `key` between elements equal under `first`.
+pub fn then_by_key<T, K, F, G>(first: F, key: G) -> impl FnMut(&T, &T) -> bool
+where
+    F: FnMut(&T, &T) -> bool,
+    G: FnMut(&T) -> K,
+    K: PartialOrd,
+{
+    then_by(first, by_key(key))
+}
+
+/// `None` before every `Some`, which are compared with `is_less`.
+pub fn nulls_first<T, F>(is_less: F) -> impl FnMut(&Option<T>, &Option<T>) -> bool
+where
+    F: FnMut(&T, &T) -> bool,
+{
+    let mut is_less = is_less;
+    move |a, b| match (a, b) {
+        (Some(a), Some(b)) => is_less(a, b),
+        (None, Some(_)) => true,
+        (_, None) => false,
+    }
+}
+
+/// `None` after every `Some`, which are compared with `is_less`.
+pub fn nulls_last<T, F>(is_less: F) -> impl FnMut(&Option<T>, &Option<T>) -> bool
+where
+    F: FnMut(&T, &T) -> bool,
+{
+    let mut is_less = is_less;
+    move |a, b| match (a, b) {
+        (Some(a), Some(b)) => is_less(a, b),
+        (Some(_), None) => true,
+        (None, _) => false,
+    }
+}
+
+/// Floats with a total order, see [`f64::total_cmp`].
+pub trait TotalOrder {
+    fn total_lt(&self, other: &Self) -> bool;
+}
+
+impl TotalOrder for f32 {
+    fn total_lt(&self, other: &Self) -> bool {
+        self.total_cmp(other).is_lt()
+    }
+}
+
+impl TotalOrder for f64 {
+    fn total_lt(&self, other: &Self) -> bool {
+        self.total_cmp(other).is_lt()
+    }
+}
+
+/// `is_less` for floats: `-NaN < -inf < ... < -0.0 < 0.0 < ... < inf < NaN`.
+///
+/// # Examples
+///
+/// ```
+/// use sort::compare::total_order;
+/// use sort::{Solution, Stable};
+/// let sorted = Stable::sort_method([f64::NAN, 1.0, -0.0, f64::NEG_INFINITY, 0.0], total_order);
+/// assert_eq!(sorted[..4], [f64::NEG_INFINITY, -0.0, 0.0, 1.0]);
+/// assert!(sorted[4].is_nan());
+/// ```
+pub fn total_order<T: TotalOrder>(a: &T, b: &T) -> bool {
+    a.total_lt(b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{SliceSolution, Solution, Stable};
+
+    type Record = (&'static str, Option<f64>, i32);
+
+    fn records() -> Vec<Record> {
+        vec![
+            ("d", Some(1.5), 2),
+            ("a", None, 1),
+            ("c", Some(f64::NAN), 2),
+            ("b", Some(-3.0), 1),
+            ("e", None, 2),
+            ("f", Some(1.5), 1),
+        ]
+    }
+
+    fn names(records: &[Record]) -> Vec<&'static str> {
+        records.iter().map(|r| r.0).collect()
+    }
+
+    #[test]
+    fn nulls() {
+        let mut vec = records();
+        Stable::sort_slice_method(
+            &mut vec,
+            by_key_with(|r: &Record| r.1, nulls_first(total_order)),
+        );
+        assert_eq!(names(&vec), ["a", "e", "b", "d", "f", "c"]);
+
+        let mut vec = records();
+        Stable::sort_slice_method(
+            &mut vec,
+            by_key_with(|r: &Record| r.1, nulls_last(total_order)),
+        );
+        assert_eq!(names(&vec), ["b", "d", "f", "c", "a", "e"]);
+    }
+
+    #[test]
+    fn multi_key() {
+        let mut vec = records();
+        let rule = then_by_key(by_key_desc(|r: &Record| r.2), |r: &Record| r.0);
+        let sorted = Stable::sort_slice_method(&mut vec, rule);
+        assert!(sorted.force_check());
+        assert_eq!(names(&sorted), ["c", "d", "e", "a", "b", "f"]);
+    }
+
+    #[test]
+    fn then_by_is_a_strict_order() {
+        let values: Vec<(i32, i32)> = (0..4).flat_map(|a| (0..4).map(move |b| (a, b))).collect();
+        let mut is_less = then_by(
+            by_key(|x: &(i32, i32)| x.0),
+            reverse(by_key(|x: &(i32, i32)| x.1)),
+        );
+        for a in &values {
+            for b in &values {
+                let expected = (a.0, std::cmp::Reverse(a.1)) < (b.0, std::cmp::Reverse(b.1));
+                assert_eq!(is_less(a, b), expected, "{:?} < {:?}", a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn stored_rule() {
+        let sorted = Stable::sort_method([3.5, -1.0, 2.0f32, 0.5], reverse(total_order));
+        assert_eq!(*sorted, [3.5, 2.0, 0.5, -1.0]);
+        assert!(sorted.force_check());
+        assert_eq!(sorted.lower_bound(&1.0), 2);
+        assert!(sorted.contains(&0.5));
+    }
+}
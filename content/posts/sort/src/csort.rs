@@ -1,45 +1,357 @@
 #[path = "./csort_bind.rs"]
+#[allow(non_camel_case_types, non_upper_case_globals)]
 mod csort_bind;
 use csort_bind as cbind;
+use std::fmt::{self, Display};
+
+/// Error of the C sorts which can fail.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CSortError {
+    /// The split index is greater than the length of the array.
+    BadSplit,
+    /// The scratch buffer could not be allocated.
+    OutOfMemory,
+}
+
+impl Display for CSortError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CSortError::BadSplit => write!(f, "split index out of bounds"),
+            CSortError::OutOfMemory => write!(f, "failed to allocate the scratch buffer"),
+        }
+    }
+}
+
+impl std::error::Error for CSortError {}
+
+fn check(code: cbind::SortError) -> Result<(), CSortError> {
+    match code {
+        cbind::SortError_SORT_OK => Ok(()),
+        cbind::SortError_SORT_BAD_SPLIT => Err(CSortError::BadSplit),
+        cbind::SortError_SORT_NO_MEMORY => Err(CSortError::OutOfMemory),
+        code => unreachable!("unknown C sort error {}", code),
+    }
+}
+
+/// Scratch space for the C sorts, left uninitialised for C to write first.
+///
+/// The C sorts used to keep it in variable length arrays on the C stack,
+/// which overflowed for a few million elements.
+fn scratch<T>(len: usize) -> Result<Vec<T>, CSortError> {
+    let mut buffer = Vec::new();
+    buffer
+        .try_reserve_exact(len)
+        .map_err(|_| CSortError::OutOfMemory)?;
+    Ok(buffer)
+}
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// Element types the C sorts are compiled for, see `c-src/each-type.h`.
+///
+/// Floats are sorted in total order, as by [`f64::total_cmp`]:
+/// `-NaN < -inf < ... < -0.0 < 0.0 < ... < inf < NaN`.
+///
+/// # Examples
+///
+/// ```
+/// use sort::csort;
+/// let mut array = [2.5, f64::NAN, 0.0, f64::NEG_INFINITY, -0.0];
+/// csort::quick_sort(&mut array);
+/// assert_eq!(array[..4], [f64::NEG_INFINITY, -0.0, 0.0, 2.5]);
+/// assert!(array[1].is_sign_negative() && array[2].is_sign_positive());
+/// assert!(array[4].is_nan());
+///
+/// let mut array = [u64::MAX, 0, 1 << 63];
+/// csort::radix_lsd_sort(&mut array).unwrap();
+/// assert_eq!(array, [0, 1 << 63, u64::MAX]);
+/// ```
+pub trait CSortable: Copy + sealed::Sealed {
+    #[doc(hidden)]
+    unsafe fn merge_two_sorted_array(
+        len: usize,
+        array: *mut Self,
+        split: usize,
+        buffer: *mut Self,
+    ) -> cbind::SortError;
+    #[doc(hidden)]
+    unsafe fn bubble_sort(len: usize, array: *mut Self);
+    #[doc(hidden)]
+    unsafe fn selection_sort(len: usize, array: *mut Self);
+    #[doc(hidden)]
+    unsafe fn insertion_sort(len: usize, array: *mut Self);
+    #[doc(hidden)]
+    unsafe fn merge_sort(len: usize, array: *mut Self, buffer: *mut Self) -> cbind::SortError;
+    #[doc(hidden)]
+    unsafe fn merge_sort_parallel(
+        len: usize,
+        array: *mut Self,
+        buffer: *mut Self,
+    ) -> cbind::SortError;
+    #[doc(hidden)]
+    unsafe fn std_qsort(len: usize, array: *mut Self);
+    #[doc(hidden)]
+    unsafe fn quick_sort(len: usize, array: *mut Self);
+    #[doc(hidden)]
+    unsafe fn radix_lsd_sort(
+        len: usize,
+        array: *mut Self,
+        buffer: *mut cbind::LinkNode,
+    ) -> cbind::SortError;
+}
+
+macro_rules! impl_csortable {
+    (
+        $t:ty,
+        $merge_two_sorted_array:ident,
+        $bubble_sort:ident,
+        $selection_sort:ident,
+        $insertion_sort:ident,
+        $merge_sort:ident,
+        $merge_sort_parallel:ident,
+        $std_qsort:ident,
+        $quick_sort:ident,
+        $radix_lsd_sort:ident,
+    ) => {
+        impl sealed::Sealed for $t {}
+
+        impl CSortable for $t {
+            unsafe fn merge_two_sorted_array(
+                len: usize,
+                array: *mut Self,
+                split: usize,
+                buffer: *mut Self,
+            ) -> cbind::SortError {
+                cbind::$merge_two_sorted_array(len, array.cast(), split, buffer.cast())
+            }
+
+            unsafe fn bubble_sort(len: usize, array: *mut Self) {
+                cbind::$bubble_sort(len, array.cast())
+            }
+
+            unsafe fn selection_sort(len: usize, array: *mut Self) {
+                cbind::$selection_sort(len, array.cast())
+            }
+
+            unsafe fn insertion_sort(len: usize, array: *mut Self) {
+                cbind::$insertion_sort(len, array.cast())
+            }
+
+            unsafe fn merge_sort(
+                len: usize,
+                array: *mut Self,
+                buffer: *mut Self,
+            ) -> cbind::SortError {
+                cbind::$merge_sort(len, array.cast(), buffer.cast())
+            }
+
+            unsafe fn merge_sort_parallel(
+                len: usize,
+                array: *mut Self,
+                buffer: *mut Self,
+            ) -> cbind::SortError {
+                cbind::$merge_sort_parallel(len, array.cast(), buffer.cast())
+            }
+
+            unsafe fn std_qsort(len: usize, array: *mut Self) {
+                cbind::$std_qsort(len, array.cast())
+            }
+
+            unsafe fn quick_sort(len: usize, array: *mut Self) {
+                cbind::$quick_sort(len, array.cast())
+            }
+
+            unsafe fn radix_lsd_sort(
+                len: usize,
+                array: *mut Self,
+                buffer: *mut cbind::LinkNode,
+            ) -> cbind::SortError {
+                cbind::$radix_lsd_sort(len, array.cast(), buffer)
+            }
+        }
+    };
+}
+
+impl_csortable!(
+    i32,
+    merge_two_sorted_array_i32,
+    bubble_sort_i32,
+    selection_sort_i32,
+    insertion_sort_i32,
+    merge_sort_i32,
+    merge_sort_parallel_i32,
+    std_qsort_i32,
+    quick_sort_i32,
+    radix_lsd_sort_i32,
+);
+impl_csortable!(
+    i64,
+    merge_two_sorted_array_i64,
+    bubble_sort_i64,
+    selection_sort_i64,
+    insertion_sort_i64,
+    merge_sort_i64,
+    merge_sort_parallel_i64,
+    std_qsort_i64,
+    quick_sort_i64,
+    radix_lsd_sort_i64,
+);
+impl_csortable!(
+    u32,
+    merge_two_sorted_array_u32,
+    bubble_sort_u32,
+    selection_sort_u32,
+    insertion_sort_u32,
+    merge_sort_u32,
+    merge_sort_parallel_u32,
+    std_qsort_u32,
+    quick_sort_u32,
+    radix_lsd_sort_u32,
+);
+impl_csortable!(
+    u64,
+    merge_two_sorted_array_u64,
+    bubble_sort_u64,
+    selection_sort_u64,
+    insertion_sort_u64,
+    merge_sort_u64,
+    merge_sort_parallel_u64,
+    std_qsort_u64,
+    quick_sort_u64,
+    radix_lsd_sort_u64,
+);
+impl_csortable!(
+    f32,
+    merge_two_sorted_array_f32,
+    bubble_sort_f32,
+    selection_sort_f32,
+    insertion_sort_f32,
+    merge_sort_f32,
+    merge_sort_parallel_f32,
+    std_qsort_f32,
+    quick_sort_f32,
+    radix_lsd_sort_f32,
+);
+impl_csortable!(
+    f64,
+    merge_two_sorted_array_f64,
+    bubble_sort_f64,
+    selection_sort_f64,
+    insertion_sort_f64,
+    merge_sort_f64,
+    merge_sort_parallel_f64,
+    std_qsort_f64,
+    quick_sort_f64,
+    radix_lsd_sort_f64,
+);
+
+/// `i32` with its original index, ordered by the value only.
+///
+/// The C sorts count its comparisons, writes and swaps, see [`take_comparisons`].
+#[derive(Debug, Clone, Copy)]
+#[repr(transparent)]
+pub(crate) struct Counted(u64);
+
+impl Counted {
+    /// The value in the high half, with the order of `i32`.
+    pub(crate) fn new(value: i32, origin: u32) -> Self {
+        Counted(((value as u32 ^ 1 << 31) as u64) << 32 | origin as u64)
+    }
+
+    pub(crate) fn value(self) -> i32 {
+        ((self.0 >> 32) as u32 ^ 1 << 31) as i32
+    }
 
-pub fn merge_two_sorted_array(array: &mut [i32], split: usize) {
-    if split > array.len() {
-        panic!("split index out of bounds");
+    pub(crate) fn origin(self) -> usize {
+        self.0 as u32 as usize
     }
+}
+
+impl_csortable!(
+    Counted,
+    merge_two_sorted_array_counted,
+    bubble_sort_counted,
+    selection_sort_counted,
+    insertion_sort_counted,
+    merge_sort_counted,
+    merge_sort_parallel_counted,
+    std_qsort_counted,
+    quick_sort_counted,
+    radix_lsd_sort_counted,
+);
+
+/// Comparisons of [`Counted`] by all threads since the last call.
+pub(crate) fn take_comparisons() -> usize {
+    unsafe { cbind::take_comparisons() }
+}
 
-    unsafe { cbind::merge_two_sorted_array(array.len() as u32, array.as_mut_ptr(), split as u32) }
+/// Elements stored by the sorts of [`Counted`] since the last call,
+/// two for every swap.
+pub(crate) fn take_writes() -> usize {
+    unsafe { cbind::take_writes() }
 }
 
-pub fn bubble_sort(array: &mut [i32]) {
-    unsafe { cbind::bubble_sort(array.len() as u32, array.as_mut_ptr()) }
+/// Swaps of [`Counted`] by all threads since the last call.
+pub(crate) fn take_swaps() -> usize {
+    unsafe { cbind::take_swaps() }
 }
 
-pub fn selection_sort(array: &mut [i32]) {
-    unsafe { cbind::selection_sort(array.len() as u32, array.as_mut_ptr()) }
+/// Merge `array[..split]` and `array[split..]`, both sorted.
+///
+/// # Examples
+///
+/// ```
+/// use sort::csort::{merge_two_sorted_array, CSortError};
+/// let mut array = [1, 3, 5, 0, 3, 4, 4, 8];
+/// assert_eq!(merge_two_sorted_array(&mut array, 3), Ok(()));
+/// assert_eq!(array, [0, 1, 3, 3, 4, 4, 5, 8]);
+/// assert_eq!(merge_two_sorted_array(&mut array, 9), Err(CSortError::BadSplit));
+/// ```
+pub fn merge_two_sorted_array<T: CSortable>(
+    array: &mut [T],
+    split: usize,
+) -> Result<(), CSortError> {
+    let mut buffer = scratch(array.len())?;
+    check(unsafe {
+        T::merge_two_sorted_array(array.len(), array.as_mut_ptr(), split, buffer.as_mut_ptr())
+    })
 }
 
-pub fn insertion_sort(array: &mut [i32]) {
-    unsafe { cbind::insertion_sort(array.len() as u32, array.as_mut_ptr()) }
+pub fn bubble_sort<T: CSortable>(array: &mut [T]) {
+    unsafe { T::bubble_sort(array.len(), array.as_mut_ptr()) }
 }
 
-pub fn merge_sort(array: &mut [i32]) {
-    unsafe { cbind::merge_sort(array.len() as u32, array.as_mut_ptr()) }
+pub fn selection_sort<T: CSortable>(array: &mut [T]) {
+    unsafe { T::selection_sort(array.len(), array.as_mut_ptr()) }
 }
 
-pub fn merge_sort_parallel(array: &mut [i32]) {
-    unsafe { cbind::merge_sort_parallel(array.len() as u32, array.as_mut_ptr()) }
+pub fn insertion_sort<T: CSortable>(array: &mut [T]) {
+    unsafe { T::insertion_sort(array.len(), array.as_mut_ptr()) }
 }
 
-pub fn radix_lsd_sort(array: &mut [i32]) {
-    unsafe { cbind::radix_lsd_sort(array.len() as u32, array.as_mut_ptr()) }
+pub fn merge_sort<T: CSortable>(array: &mut [T]) -> Result<(), CSortError> {
+    let mut buffer = scratch(array.len())?;
+    check(unsafe { T::merge_sort(array.len(), array.as_mut_ptr(), buffer.as_mut_ptr()) })
 }
 
-pub fn cstd_qsort(array: &mut [i32]) {
-    unsafe { cbind::std_qsort(array.len() as u32, array.as_mut_ptr()) }
+pub fn merge_sort_parallel<T: CSortable>(array: &mut [T]) -> Result<(), CSortError> {
+    let mut buffer = scratch(array.len())?;
+    check(unsafe { T::merge_sort_parallel(array.len(), array.as_mut_ptr(), buffer.as_mut_ptr()) })
 }
 
-pub fn quick_sort(array: &mut [i32]) {
-    unsafe { cbind::quick_sort(array.len() as u32, array.as_mut_ptr()) }
+pub fn radix_lsd_sort<T: CSortable>(array: &mut [T]) -> Result<(), CSortError> {
+    let mut buffer = scratch(array.len())?;
+    check(unsafe { T::radix_lsd_sort(array.len(), array.as_mut_ptr(), buffer.as_mut_ptr()) })
+}
+
+pub fn cstd_qsort<T: CSortable>(array: &mut [T]) {
+    unsafe { T::std_qsort(array.len(), array.as_mut_ptr()) }
+}
+
+pub fn quick_sort<T: CSortable>(array: &mut [T]) {
+    unsafe { T::quick_sort(array.len(), array.as_mut_ptr()) }
 }
 
 #[cfg(test)]
@@ -47,109 +359,160 @@ mod tests {
     use super::*;
 
     mod utils {
+        use super::CSortable;
         use crate::read_bench_data;
+        use std::fmt::Debug;
+
+        /// Element type the tests run for, made from the `i32` test data.
+        pub(super) trait Element: CSortable + PartialEq + Debug {
+            /// Map preserving the order of `i32`.
+            fn from_i32(x: i32) -> Self;
+            /// Sort with the standard library as the expected result.
+            fn sort_std(slice: &mut [Self]);
+        }
+
+        macro_rules! element_int {
+            ($t:ty, $from_i32:expr) => {
+                impl Element for $t {
+                    fn from_i32(x: i32) -> Self {
+                        $from_i32(x)
+                    }
+
+                    fn sort_std(slice: &mut [Self]) {
+                        slice.sort();
+                    }
+                }
+            };
+        }
+
+        macro_rules! element_float {
+            ($t:ty, $from_i32:expr) => {
+                impl Element for $t {
+                    fn from_i32(x: i32) -> Self {
+                        $from_i32(x)
+                    }
+
+                    fn sort_std(slice: &mut [Self]) {
+                        slice.sort_by(<$t>::total_cmp);
+                    }
+                }
+            };
+        }
 
-        fn test_empty(solution: fn(&mut [i32])) {
-            let mut data = [];
+        element_int!(i32, |x| x);
+        element_int!(i64, |x: i32| (x as i64) << 32);
+        element_int!(u32, |x: i32| x as u32 ^ 1 << 31);
+        element_int!(u64, |x: i32| ((x as i64) << 32) as u64 ^ 1 << 63);
+        element_float!(f32, |x: i32| x as f32 / 8.0);
+        element_float!(f64, |x: i32| x as f64 / 1024.0);
+
+        fn test_empty<T: Element>(solution: fn(&mut [T])) {
+            let mut data: [T; 0] = [];
             solution(&mut data);
-            assert_eq!(data, [] as [i32; 0]);
+            assert_eq!(data, []);
         }
 
-        fn test_one(solution: fn(&mut [i32])) {
-            let mut data = [1];
+        fn test_one<T: Element>(solution: fn(&mut [T])) {
+            let mut data = [1].map(T::from_i32);
             solution(&mut data);
-            assert_eq!(data, [1]);
+            assert_eq!(data, [1].map(T::from_i32));
         }
 
-        fn test_two(solution: fn(&mut [i32])) {
-            let mut data = [2, 1];
+        fn test_two<T: Element>(solution: fn(&mut [T])) {
+            let mut data = [2, 1].map(T::from_i32);
             solution(&mut data);
-            assert_eq!(data, [1, 2]);
+            assert_eq!(data, [1, 2].map(T::from_i32));
         }
 
-        fn test_three(solution: fn(&mut [i32])) {
-            let mut data = [3, 1, 2];
+        fn test_three<T: Element>(solution: fn(&mut [T])) {
+            let mut data = [3, 1, 2].map(T::from_i32);
             solution(&mut data);
-            assert_eq!(data, [1, 2, 3]);
+            assert_eq!(data, [1, 2, 3].map(T::from_i32));
         }
 
-        fn test_four(solution: fn(&mut [i32])) {
-            let mut data = [2, 3, 2, 1];
+        fn test_four<T: Element>(solution: fn(&mut [T])) {
+            let mut data = [2, 3, 2, 1].map(T::from_i32);
             solution(&mut data);
-            assert_eq!(data, [1, 2, 2, 3]);
+            assert_eq!(data, [1, 2, 2, 3].map(T::from_i32));
         }
 
-        fn test_five(solution: fn(&mut [i32])) {
-            let mut data = [5, 1, 4, 2, 3];
+        fn test_five<T: Element>(solution: fn(&mut [T])) {
+            let mut data = [5, 1, 4, 2, 3].map(T::from_i32);
             solution(&mut data);
-            assert_eq!(data, [1, 2, 3, 4, 5]);
+            assert_eq!(data, [1, 2, 3, 4, 5].map(T::from_i32));
         }
 
-        fn test_origin(solution: fn(&mut [i32])) {
-            let mut data = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9];
+        fn test_origin<T: Element>(solution: fn(&mut [T])) {
+            let mut data = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9].map(T::from_i32);
             let mut sort = data;
-            data.sort();
+            T::sort_std(&mut data);
             solution(&mut sort);
             assert_eq!(sort, data);
         }
 
-        fn test_simple(solution: fn(&mut [i32])) {
-            let mut data = [9, 3, 5, 1, 7, 4, 6, 2, 8, 0];
+        fn test_simple<T: Element>(solution: fn(&mut [T])) {
+            let mut data = [9, 3, 5, 1, 7, 4, 6, 2, 8, 0].map(T::from_i32);
             let mut sort = data;
-            data.sort();
+            T::sort_std(&mut data);
             solution(&mut sort);
             assert_eq!(sort, data);
         }
 
-        fn test_random(solution: fn(&mut [i32])) {
-            let mut data: [i32; 10000] = read_bench_data!("random");
+        fn test_random<T: Element>(solution: fn(&mut [T])) {
+            let data: [i32; 10000] = read_bench_data!("random");
+            let mut data = data.map(T::from_i32);
             let mut sort = data;
             solution(&mut sort);
-            data.sort();
+            T::sort_std(&mut data);
             assert_eq!(sort, data);
         }
 
-        fn test_stroll(solution: fn(&mut [i32])) {
-            let mut data: [i32; 10000] = read_bench_data!("stroll");
+        fn test_stroll<T: Element>(solution: fn(&mut [T])) {
+            let data: [i32; 10000] = read_bench_data!("stroll");
+            let mut data = data.map(T::from_i32);
             let mut sort = data;
             solution(&mut sort);
-            data.sort();
+            T::sort_std(&mut data);
             assert_eq!(sort, data);
         }
 
-        fn test_trend_increasing(solution: fn(&mut [i32])) {
-            let mut data: [i32; 1000] = read_bench_data!("trend-increasing");
+        fn test_trend_increasing<T: Element>(solution: fn(&mut [T])) {
+            let data: [i32; 1000] = read_bench_data!("trend-increasing");
+            let mut data = data.map(T::from_i32);
             let mut sort = data;
             solution(&mut sort);
-            data.sort();
+            T::sort_std(&mut data);
             assert_eq!(sort, data);
         }
 
-        fn test_gaussian_with_noise(solution: fn(&mut [i32])) {
-            let mut data: [i32; 1000] = read_bench_data!("gaussian-with-noise");
+        fn test_gaussian_with_noise<T: Element>(solution: fn(&mut [T])) {
+            let data: [i32; 1000] = read_bench_data!("gaussian-with-noise");
+            let mut data = data.map(T::from_i32);
             let mut sort = data;
             solution(&mut sort);
-            data.sort();
+            T::sort_std(&mut data);
             assert_eq!(sort, data);
         }
 
-        fn test_low_sample_sin_with_noise(solution: fn(&mut [i32])) {
-            let mut data: [i32; 1000] = read_bench_data!("low-sample-sin-with-noise");
+        fn test_low_sample_sin_with_noise<T: Element>(solution: fn(&mut [T])) {
+            let data: [i32; 1000] = read_bench_data!("low-sample-sin-with-noise");
+            let mut data = data.map(T::from_i32);
             let mut sort = data;
             solution(&mut sort);
-            data.sort();
+            T::sort_std(&mut data);
             assert_eq!(sort, data);
         }
 
-        fn test_high_sample_sin_with_noise(solution: fn(&mut [i32])) {
-            let mut data: [i32; 1000] = read_bench_data!("high-sample-sin-with-noise");
+        fn test_high_sample_sin_with_noise<T: Element>(solution: fn(&mut [T])) {
+            let data: [i32; 1000] = read_bench_data!("high-sample-sin-with-noise");
+            let mut data = data.map(T::from_i32);
             let mut sort = data;
             solution(&mut sort);
-            data.sort();
+            T::sort_std(&mut data);
             assert_eq!(sort, data);
         }
 
-        pub(super) fn test_all(solution: fn(&mut [i32])) {
+        pub(super) fn test_all<T: Element>(solution: fn(&mut [T])) {
             test_empty(solution);
             test_one(solution);
             test_two(solution);
@@ -167,224 +530,196 @@ mod tests {
         }
     }
 
+    /// Run [`utils::test_all`] for every [`CSortable`] type.
+    macro_rules! test_all_types {
+        ($solution:expr) => {
+            utils::test_all::<i32>($solution);
+            utils::test_all::<i64>($solution);
+            utils::test_all::<u32>($solution);
+            utils::test_all::<u64>($solution);
+            utils::test_all::<f32>($solution);
+            utils::test_all::<f64>($solution);
+        };
+    }
+
     #[test]
     fn bubble() {
-        utils::test_all(bubble_sort);
+        test_all_types!(bubble_sort);
     }
 
     #[test]
     fn selection() {
-        utils::test_all(selection_sort);
+        test_all_types!(selection_sort);
     }
 
     #[test]
     fn insertion() {
-        utils::test_all(insertion_sort);
+        test_all_types!(insertion_sort);
     }
 
     #[test]
     fn merge() {
-        utils::test_all(merge_sort);
+        test_all_types!(|array| merge_sort(array).unwrap());
     }
 
     #[test]
     fn merge_parallel() {
-        utils::test_all(merge_sort_parallel);
+        test_all_types!(|array| merge_sort_parallel(array).unwrap());
     }
 
     #[test]
     fn radix_lsd() {
-        utils::test_all(radix_lsd_sort);
+        test_all_types!(|array| radix_lsd_sort(array).unwrap());
     }
 
     #[test]
-    fn qsort_cstd() {
-        utils::test_all(cstd_qsort);
+    fn merge_two() {
+        let mut array = [2, 4, 6, 1, 3, 5];
+        assert_eq!(merge_two_sorted_array(&mut array, 6), Ok(()));
+        assert_eq!(array, [2, 4, 6, 1, 3, 5]);
+        assert_eq!(merge_two_sorted_array(&mut array, 3), Ok(()));
+        assert_eq!(array, [1, 2, 3, 4, 5, 6]);
+
+        assert_eq!(
+            merge_two_sorted_array(&mut array, 7),
+            Err(CSortError::BadSplit)
+        );
+        assert_eq!(
+            merge_two_sorted_array::<i32>(&mut [], 1),
+            Err(CSortError::BadSplit)
+        );
+        assert_eq!(array, [1, 2, 3, 4, 5, 6]);
     }
 
     #[test]
-    fn quick() {
-        utils::test_all(quick_sort);
+    fn float_total_order() {
+        let data = [
+            f64::NAN,
+            1.5,
+            -0.0,
+            f64::INFINITY,
+            -f64::NAN,
+            0.0,
+            -2.0,
+            f64::NEG_INFINITY,
+            0.0,
+            3.0,
+        ];
+        let mut expected = data;
+        expected.sort_by(f64::total_cmp);
+
+        let solutions: [fn(&mut [f64]); 8] = [
+            bubble_sort,
+            selection_sort,
+            insertion_sort,
+            |array| merge_sort(array).unwrap(),
+            |array| merge_sort_parallel(array).unwrap(),
+            |array| radix_lsd_sort(array).unwrap(),
+            cstd_qsort,
+            quick_sort,
+        ];
+        for solution in solutions {
+            let mut array = data;
+            solution(&mut array);
+            assert_eq!(array.map(f64::to_bits), expected.map(f64::to_bits));
+        }
     }
 
-    mod bench {
+    mod stability {
         use super::*;
-        use crate::read_bench_data;
-        extern crate test;
-
-        fn bench_random(b: &mut test::Bencher, solution: fn(&mut [i32])) {
-            let mut data: [i32; 10000] = read_bench_data!("random");
-            b.iter(|| solution(&mut data));
-        }
-
-        fn bench_stroll(b: &mut test::Bencher, solution: fn(&mut [i32])) {
-            let mut data: [i32; 10000] = read_bench_data!("stroll");
-            b.iter(|| solution(&mut data));
-        }
-
-        fn bench_trend_increasing(b: &mut test::Bencher, solution: fn(&mut [i32])) {
-            let mut data: [i32; 1000] = read_bench_data!("trend-increasing");
-            b.iter(|| solution(&mut data));
-        }
-
-        fn bench_gaussian_with_noise(b: &mut test::Bencher, solution: fn(&mut [i32])) {
-            let mut data: [i32; 1000] = read_bench_data!("gaussian-with-noise");
-            b.iter(|| solution(&mut data));
-        }
-
-        fn bench_low_sample_sin_with_noise(b: &mut test::Bencher, solution: fn(&mut [i32])) {
-            let mut data: [i32; 1000] = read_bench_data!("low-sample-sin-with-noise");
-            b.iter(|| solution(&mut data));
-        }
-
-        fn bench_high_sample_sin_with_noise(b: &mut test::Bencher, solution: fn(&mut [i32])) {
-            let mut data: [i32; 1000] = read_bench_data!("high-sample-sin-with-noise");
-            b.iter(|| solution(&mut data));
-        }
-
-        macro_rules! bench_data {
-            ($solution:ident, $name:ident) => {
-                #[bench]
-                fn $name(b: &mut test::Bencher) {
-                    concat_idents!(bench_, $name)(b, $solution);
-                }
-            };
-        }
-
-        macro_rules! bench_all {
-            ($solution:ident, $($name:ident),*) => {
-                $( bench_data!($solution, $name);)*
-            };
-        }
-
-        mod merge {
-            use super::*;
-
-            bench_all!(
-                merge_sort,
-                random,
-                stroll,
-                trend_increasing,
-                gaussian_with_noise,
-                low_sample_sin_with_noise,
-                high_sample_sin_with_noise
-            );
-        }
-
-        mod merge_parallel {
-            use super::*;
-
-            bench_all!(
-                merge_sort_parallel,
-                random,
-                stroll,
-                trend_increasing,
-                gaussian_with_noise,
-                low_sample_sin_with_noise,
-                high_sample_sin_with_noise
-            );
+        use crate::test_utils::{bench_data, test_packed_stable};
+
+        /// `u64` ordered only by its high half, see `c-src/sort.h`.
+        #[derive(Clone, Copy)]
+        #[repr(transparent)]
+        struct Keyed(u64);
+
+        impl_csortable!(
+            Keyed,
+            merge_two_sorted_array_keyed,
+            bubble_sort_keyed,
+            selection_sort_keyed,
+            insertion_sort_keyed,
+            merge_sort_keyed,
+            merge_sort_parallel_keyed,
+            std_qsort_keyed,
+            quick_sort_keyed,
+            radix_lsd_sort_keyed,
+        );
+
+        fn test_stable(solution: fn(&mut [Keyed])) {
+            for (_, data) in bench_data() {
+                test_packed_stable(
+                    |packed| {
+                        let mut keyed: Vec<Keyed> = packed.iter().map(|&r| Keyed(r)).collect();
+                        solution(&mut keyed);
+                        packed
+                            .iter_mut()
+                            .zip(keyed)
+                            .for_each(|(r, Keyed(sorted))| *r = sorted);
+                    },
+                    &data,
+                );
+            }
         }
 
-        mod bubble {
-            use super::*;
-
-            bench_all!(
-                bubble_sort,
-                random,
-                stroll,
-                trend_increasing,
-                gaussian_with_noise,
-                low_sample_sin_with_noise,
-                high_sample_sin_with_noise
-            );
+        #[test]
+        fn bubble() {
+            test_stable(bubble_sort);
         }
 
-        mod selection {
-            use super::*;
-
-            bench_all!(
-                selection_sort,
-                random,
-                stroll,
-                trend_increasing,
-                gaussian_with_noise,
-                low_sample_sin_with_noise,
-                high_sample_sin_with_noise
-            );
+        #[test]
+        fn insertion() {
+            test_stable(insertion_sort);
         }
 
-        mod insertion {
-            use super::*;
-
-            bench_all!(
-                insertion_sort,
-                random,
-                stroll,
-                trend_increasing,
-                gaussian_with_noise,
-                low_sample_sin_with_noise,
-                high_sample_sin_with_noise
-            );
+        #[test]
+        fn merge() {
+            test_stable(|array| merge_sort(array).unwrap());
         }
 
-        mod radix_lsd {
-            use super::*;
-
-            bench_all!(
-                radix_lsd_sort,
-                random,
-                stroll,
-                trend_increasing,
-                gaussian_with_noise,
-                low_sample_sin_with_noise,
-                high_sample_sin_with_noise
-            );
+        #[test]
+        fn merge_parallel() {
+            test_stable(|array| merge_sort_parallel(array).unwrap());
         }
 
-        mod cstd_qsort {
-            use super::*;
-
-            bench_all!(
-                cstd_qsort,
-                random,
-                stroll,
-                trend_increasing,
-                gaussian_with_noise,
-                low_sample_sin_with_noise,
-                high_sample_sin_with_noise
-            );
+        #[test]
+        fn radix_lsd() {
+            test_stable(|array| radix_lsd_sort(array).unwrap());
         }
+    }
 
-        mod rust_stable {
-            use super::*;
-
-            fn rust_stable(data: &mut [i32]) {
-                data.sort();
-            }
+    #[test]
+    fn qsort_cstd() {
+        test_all_types!(cstd_qsort);
+    }
 
-            bench_all!(
-                rust_stable,
-                random,
-                stroll,
-                trend_increasing,
-                gaussian_with_noise,
-                low_sample_sin_with_noise,
-                high_sample_sin_with_noise
-            );
-        }
+    #[test]
+    fn quick() {
+        test_all_types!(quick_sort);
+    }
 
-        mod quick_sort {
-            use super::*;
-
-            bench_all!(
-                quick_sort,
-                random,
-                stroll,
-                trend_increasing,
-                gaussian_with_noise,
-                low_sample_sin_with_noise,
-                high_sample_sin_with_noise
-            );
-        }
+    /// The scratch space of 50M elements would overflow the C stack.
+    #[test]
+    fn large() {
+        const LEN: i64 = 50_000_000;
+        let expected = -LEN / 2..LEN / 2;
+        // 7919 is coprime to LEN, so this is a permutation of `expected`
+        let data: Vec<i32> = (0..LEN)
+            .map(|x| (x * 7919 % LEN - LEN / 2) as i32)
+            .collect();
+
+        for solution in [merge_sort, merge_sort_parallel, radix_lsd_sort] {
+            let mut array = data.clone();
+            solution(&mut array).unwrap();
+            assert!(array.iter().map(|&x| x as i64).eq(expected.clone()));
+        }
+
+        // evens then odds
+        let mut array: Vec<i32> = (0..LEN)
+            .map(|x| (x % (LEN / 2) * 2 + x / (LEN / 2) - LEN / 2) as i32)
+            .collect();
+        merge_two_sorted_array(&mut array, LEN as usize / 2).unwrap();
+        assert!(array.iter().map(|&x| x as i64).eq(expected));
     }
 }
@@ -1,42 +1,406 @@
 /* automatically generated by rust-bindgen 0.70.1 */
 
+pub type __int32_t = ::std::os::raw::c_int;
+pub type __uint32_t = ::std::os::raw::c_uint;
+pub type __int64_t = ::std::os::raw::c_long;
+pub type __uint64_t = ::std::os::raw::c_ulong;
+pub type int32_t = __int32_t;
+pub type int64_t = __int64_t;
+pub type uint32_t = __uint32_t;
+pub type uint64_t = __uint64_t;
+pub const SortError_SORT_OK: SortError = 0;
+#[doc = " `split` is greater than `len`."]
+pub const SortError_SORT_BAD_SPLIT: SortError = 1;
+#[doc = " The scratch buffer could not be allocated."]
+pub const SortError_SORT_NO_MEMORY: SortError = 2;
+#[doc = " Result of the sorts which can fail."]
+pub type SortError = ::std::os::raw::c_uint;
+#[doc = " Node of the linked lists used by radix sort."]
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct LinkNode {
+    pub data: u64,
+    pub next: *mut LinkNode,
+}
+#[allow(clippy::unnecessary_operation, clippy::identity_op)]
+const _: () = {
+    ["Size of LinkNode"][::std::mem::size_of::<LinkNode>() - 16usize];
+    ["Alignment of LinkNode"][::std::mem::align_of::<LinkNode>() - 8usize];
+    ["Offset of field: LinkNode::data"][::std::mem::offset_of!(LinkNode, data) - 0usize];
+    ["Offset of field: LinkNode::next"][::std::mem::offset_of!(LinkNode, next) - 8usize];
+};
+extern "C" {
+    #[doc = " Return the number of comparisons made by the sorts of the counted type,\n and start counting again from 0."]
+    pub fn take_comparisons() -> usize;
+}
+extern "C" {
+    #[doc = " Return the number of elements stored by the sorts of the counted type,\n two for every swap, and start counting again from 0."]
+    pub fn take_writes() -> usize;
+}
+extern "C" {
+    #[doc = " Return the number of swaps made by the sorts of the counted type,\n and start counting again from 0."]
+    pub fn take_swaps() -> usize;
+}
+extern "C" {
+    #[doc = " Merge two sorted array into one sorted array\n\n `buffer` is scratch space of at least `len` elements,\n or NULL to allocate it on the heap.\n\n Return `SORT_BAD_SPLIT` if `split > len`.\n\n # Example\n\n int array[] = {1, 3, 5, 0, 3, 4, 4, 8};\n //                     ^split        ^len\n merge_two_sorted_array_i32(8, array, 3, NULL);\n // array = {0, 1, 3, 3, 4, 4, 5, 8}"]
+    pub fn merge_two_sorted_array_i32(
+        len: usize,
+        array: *mut int32_t,
+        split: usize,
+        buffer: *mut int32_t,
+    ) -> SortError;
+}
+extern "C" {
+    #[doc = " Bubble sort\n\n # Example\n\n int array[] = {1, 3, 5, 0, 3, 4, 4, 8};\n bubble_sort_i32(8, array);\n // array = {0, 1, 3, 3, 4, 4, 5, 8}"]
+    pub fn bubble_sort_i32(len: usize, array: *mut int32_t);
+}
+extern "C" {
+    #[doc = " Selection sort\n\n # Example\n\n int array[] = {1, 3, 5, 0, 3, 4, 4, 8};\n selection_sort_i32(8, array);\n // array = {0, 1, 3, 3, 4, 4, 5, 8}"]
+    pub fn selection_sort_i32(len: usize, array: *mut int32_t);
+}
+extern "C" {
+    #[doc = " Insertion sort\n\n # Example\n\n int array[] = {1, 3, 5, 0, 3, 4, 4, 8};\n insertion_sort_i32(8, array);\n // array = {0, 1, 3, 3, 4, 4, 5, 8}"]
+    pub fn insertion_sort_i32(len: usize, array: *mut int32_t);
+}
+extern "C" {
+    #[doc = " Merge sort\n\n `buffer` is scratch space of at least `len` elements,\n or NULL to allocate it on the heap.\n\n # Example\n\n int array[] = {1, 3, 5, 0, 3, 4, 4, 8};\n merge_sort_i32(8, array, NULL);\n // array = {0, 1, 3, 3, 4, 4, 5, 8}"]
+    pub fn merge_sort_i32(len: usize, array: *mut int32_t, buffer: *mut int32_t) -> SortError;
+}
+extern "C" {
+    #[doc = " Merge sort parallel.\n\n `buffer` is as for `merge_sort`."]
+    pub fn merge_sort_parallel_i32(
+        len: usize,
+        array: *mut int32_t,
+        buffer: *mut int32_t,
+    ) -> SortError;
+}
+extern "C" {
+    #[doc = " c std qsort"]
+    pub fn std_qsort_i32(len: usize, array: *mut int32_t);
+}
+extern "C" {
+    #[doc = " Quick sort"]
+    pub fn quick_sort_i32(len: usize, array: *mut int32_t);
+}
+extern "C" {
+    #[doc = " Radix LSD Sort\n\n Sort the keys of the elements (see sort.h) with base 256,\n the number of keys is KEY_BYTES, see each-type.h\n radix_lsd_sort_with(len, keys[len], 256, KEY_BYTES, buffer);\n\n `buffer` is scratch space of at least `len` nodes,\n or NULL to allocate it on the heap.\n\n # Example\n\n int array[] = {1, 3, 5, 0, 3, 4, 4, 8};\n radix_lsd_sort_i32(8, array, NULL);\n // array = {0, 1, 3, 3, 4, 4, 5, 8}"]
+    pub fn radix_lsd_sort_i32(len: usize, array: *mut int32_t, buffer: *mut LinkNode) -> SortError;
+}
+extern "C" {
+    #[doc = " Merge two sorted array into one sorted array\n\n `buffer` is scratch space of at least `len` elements,\n or NULL to allocate it on the heap.\n\n Return `SORT_BAD_SPLIT` if `split > len`.\n\n # Example\n\n int array[] = {1, 3, 5, 0, 3, 4, 4, 8};\n //                     ^split        ^len\n merge_two_sorted_array_i32(8, array, 3, NULL);\n // array = {0, 1, 3, 3, 4, 4, 5, 8}"]
+    pub fn merge_two_sorted_array_i64(
+        len: usize,
+        array: *mut int64_t,
+        split: usize,
+        buffer: *mut int64_t,
+    ) -> SortError;
+}
+extern "C" {
+    #[doc = " Bubble sort\n\n # Example\n\n int array[] = {1, 3, 5, 0, 3, 4, 4, 8};\n bubble_sort_i32(8, array);\n // array = {0, 1, 3, 3, 4, 4, 5, 8}"]
+    pub fn bubble_sort_i64(len: usize, array: *mut int64_t);
+}
+extern "C" {
+    #[doc = " Selection sort\n\n # Example\n\n int array[] = {1, 3, 5, 0, 3, 4, 4, 8};\n selection_sort_i32(8, array);\n // array = {0, 1, 3, 3, 4, 4, 5, 8}"]
+    pub fn selection_sort_i64(len: usize, array: *mut int64_t);
+}
+extern "C" {
+    #[doc = " Insertion sort\n\n # Example\n\n int array[] = {1, 3, 5, 0, 3, 4, 4, 8};\n insertion_sort_i32(8, array);\n // array = {0, 1, 3, 3, 4, 4, 5, 8}"]
+    pub fn insertion_sort_i64(len: usize, array: *mut int64_t);
+}
+extern "C" {
+    #[doc = " Merge sort\n\n `buffer` is scratch space of at least `len` elements,\n or NULL to allocate it on the heap.\n\n # Example\n\n int array[] = {1, 3, 5, 0, 3, 4, 4, 8};\n merge_sort_i32(8, array, NULL);\n // array = {0, 1, 3, 3, 4, 4, 5, 8}"]
+    pub fn merge_sort_i64(len: usize, array: *mut int64_t, buffer: *mut int64_t) -> SortError;
+}
+extern "C" {
+    #[doc = " Merge sort parallel.\n\n `buffer` is as for `merge_sort`."]
+    pub fn merge_sort_parallel_i64(
+        len: usize,
+        array: *mut int64_t,
+        buffer: *mut int64_t,
+    ) -> SortError;
+}
+extern "C" {
+    #[doc = " c std qsort"]
+    pub fn std_qsort_i64(len: usize, array: *mut int64_t);
+}
+extern "C" {
+    #[doc = " Quick sort"]
+    pub fn quick_sort_i64(len: usize, array: *mut int64_t);
+}
+extern "C" {
+    #[doc = " Radix LSD Sort\n\n Sort the keys of the elements (see sort.h) with base 256,\n the number of keys is KEY_BYTES, see each-type.h\n radix_lsd_sort_with(len, keys[len], 256, KEY_BYTES, buffer);\n\n `buffer` is scratch space of at least `len` nodes,\n or NULL to allocate it on the heap.\n\n # Example\n\n int array[] = {1, 3, 5, 0, 3, 4, 4, 8};\n radix_lsd_sort_i32(8, array, NULL);\n // array = {0, 1, 3, 3, 4, 4, 5, 8}"]
+    pub fn radix_lsd_sort_i64(len: usize, array: *mut int64_t, buffer: *mut LinkNode) -> SortError;
+}
+extern "C" {
+    #[doc = " Merge two sorted array into one sorted array\n\n `buffer` is scratch space of at least `len` elements,\n or NULL to allocate it on the heap.\n\n Return `SORT_BAD_SPLIT` if `split > len`.\n\n # Example\n\n int array[] = {1, 3, 5, 0, 3, 4, 4, 8};\n //                     ^split        ^len\n merge_two_sorted_array_i32(8, array, 3, NULL);\n // array = {0, 1, 3, 3, 4, 4, 5, 8}"]
+    pub fn merge_two_sorted_array_u32(
+        len: usize,
+        array: *mut uint32_t,
+        split: usize,
+        buffer: *mut uint32_t,
+    ) -> SortError;
+}
+extern "C" {
+    #[doc = " Bubble sort\n\n # Example\n\n int array[] = {1, 3, 5, 0, 3, 4, 4, 8};\n bubble_sort_i32(8, array);\n // array = {0, 1, 3, 3, 4, 4, 5, 8}"]
+    pub fn bubble_sort_u32(len: usize, array: *mut uint32_t);
+}
+extern "C" {
+    #[doc = " Selection sort\n\n # Example\n\n int array[] = {1, 3, 5, 0, 3, 4, 4, 8};\n selection_sort_i32(8, array);\n // array = {0, 1, 3, 3, 4, 4, 5, 8}"]
+    pub fn selection_sort_u32(len: usize, array: *mut uint32_t);
+}
+extern "C" {
+    #[doc = " Insertion sort\n\n # Example\n\n int array[] = {1, 3, 5, 0, 3, 4, 4, 8};\n insertion_sort_i32(8, array);\n // array = {0, 1, 3, 3, 4, 4, 5, 8}"]
+    pub fn insertion_sort_u32(len: usize, array: *mut uint32_t);
+}
+extern "C" {
+    #[doc = " Merge sort\n\n `buffer` is scratch space of at least `len` elements,\n or NULL to allocate it on the heap.\n\n # Example\n\n int array[] = {1, 3, 5, 0, 3, 4, 4, 8};\n merge_sort_i32(8, array, NULL);\n // array = {0, 1, 3, 3, 4, 4, 5, 8}"]
+    pub fn merge_sort_u32(len: usize, array: *mut uint32_t, buffer: *mut uint32_t) -> SortError;
+}
+extern "C" {
+    #[doc = " Merge sort parallel.\n\n `buffer` is as for `merge_sort`."]
+    pub fn merge_sort_parallel_u32(
+        len: usize,
+        array: *mut uint32_t,
+        buffer: *mut uint32_t,
+    ) -> SortError;
+}
+extern "C" {
+    #[doc = " c std qsort"]
+    pub fn std_qsort_u32(len: usize, array: *mut uint32_t);
+}
+extern "C" {
+    #[doc = " Quick sort"]
+    pub fn quick_sort_u32(len: usize, array: *mut uint32_t);
+}
+extern "C" {
+    #[doc = " Radix LSD Sort\n\n Sort the keys of the elements (see sort.h) with base 256,\n the number of keys is KEY_BYTES, see each-type.h\n radix_lsd_sort_with(len, keys[len], 256, KEY_BYTES, buffer);\n\n `buffer` is scratch space of at least `len` nodes,\n or NULL to allocate it on the heap.\n\n # Example\n\n int array[] = {1, 3, 5, 0, 3, 4, 4, 8};\n radix_lsd_sort_i32(8, array, NULL);\n // array = {0, 1, 3, 3, 4, 4, 5, 8}"]
+    pub fn radix_lsd_sort_u32(len: usize, array: *mut uint32_t, buffer: *mut LinkNode)
+        -> SortError;
+}
+extern "C" {
+    #[doc = " Merge two sorted array into one sorted array\n\n `buffer` is scratch space of at least `len` elements,\n or NULL to allocate it on the heap.\n\n Return `SORT_BAD_SPLIT` if `split > len`.\n\n # Example\n\n int array[] = {1, 3, 5, 0, 3, 4, 4, 8};\n //                     ^split        ^len\n merge_two_sorted_array_i32(8, array, 3, NULL);\n // array = {0, 1, 3, 3, 4, 4, 5, 8}"]
+    pub fn merge_two_sorted_array_u64(
+        len: usize,
+        array: *mut uint64_t,
+        split: usize,
+        buffer: *mut uint64_t,
+    ) -> SortError;
+}
+extern "C" {
+    #[doc = " Bubble sort\n\n # Example\n\n int array[] = {1, 3, 5, 0, 3, 4, 4, 8};\n bubble_sort_i32(8, array);\n // array = {0, 1, 3, 3, 4, 4, 5, 8}"]
+    pub fn bubble_sort_u64(len: usize, array: *mut uint64_t);
+}
+extern "C" {
+    #[doc = " Selection sort\n\n # Example\n\n int array[] = {1, 3, 5, 0, 3, 4, 4, 8};\n selection_sort_i32(8, array);\n // array = {0, 1, 3, 3, 4, 4, 5, 8}"]
+    pub fn selection_sort_u64(len: usize, array: *mut uint64_t);
+}
+extern "C" {
+    #[doc = " Insertion sort\n\n # Example\n\n int array[] = {1, 3, 5, 0, 3, 4, 4, 8};\n insertion_sort_i32(8, array);\n // array = {0, 1, 3, 3, 4, 4, 5, 8}"]
+    pub fn insertion_sort_u64(len: usize, array: *mut uint64_t);
+}
+extern "C" {
+    #[doc = " Merge sort\n\n `buffer` is scratch space of at least `len` elements,\n or NULL to allocate it on the heap.\n\n # Example\n\n int array[] = {1, 3, 5, 0, 3, 4, 4, 8};\n merge_sort_i32(8, array, NULL);\n // array = {0, 1, 3, 3, 4, 4, 5, 8}"]
+    pub fn merge_sort_u64(len: usize, array: *mut uint64_t, buffer: *mut uint64_t) -> SortError;
+}
+extern "C" {
+    #[doc = " Merge sort parallel.\n\n `buffer` is as for `merge_sort`."]
+    pub fn merge_sort_parallel_u64(
+        len: usize,
+        array: *mut uint64_t,
+        buffer: *mut uint64_t,
+    ) -> SortError;
+}
+extern "C" {
+    #[doc = " c std qsort"]
+    pub fn std_qsort_u64(len: usize, array: *mut uint64_t);
+}
+extern "C" {
+    #[doc = " Quick sort"]
+    pub fn quick_sort_u64(len: usize, array: *mut uint64_t);
+}
+extern "C" {
+    #[doc = " Radix LSD Sort\n\n Sort the keys of the elements (see sort.h) with base 256,\n the number of keys is KEY_BYTES, see each-type.h\n radix_lsd_sort_with(len, keys[len], 256, KEY_BYTES, buffer);\n\n `buffer` is scratch space of at least `len` nodes,\n or NULL to allocate it on the heap.\n\n # Example\n\n int array[] = {1, 3, 5, 0, 3, 4, 4, 8};\n radix_lsd_sort_i32(8, array, NULL);\n // array = {0, 1, 3, 3, 4, 4, 5, 8}"]
+    pub fn radix_lsd_sort_u64(len: usize, array: *mut uint64_t, buffer: *mut LinkNode)
+        -> SortError;
+}
+extern "C" {
+    #[doc = " Merge two sorted array into one sorted array\n\n `buffer` is scratch space of at least `len` elements,\n or NULL to allocate it on the heap.\n\n Return `SORT_BAD_SPLIT` if `split > len`.\n\n # Example\n\n int array[] = {1, 3, 5, 0, 3, 4, 4, 8};\n //                     ^split        ^len\n merge_two_sorted_array_i32(8, array, 3, NULL);\n // array = {0, 1, 3, 3, 4, 4, 5, 8}"]
+    pub fn merge_two_sorted_array_f32(
+        len: usize,
+        array: *mut f32,
+        split: usize,
+        buffer: *mut f32,
+    ) -> SortError;
+}
+extern "C" {
+    #[doc = " Bubble sort\n\n # Example\n\n int array[] = {1, 3, 5, 0, 3, 4, 4, 8};\n bubble_sort_i32(8, array);\n // array = {0, 1, 3, 3, 4, 4, 5, 8}"]
+    pub fn bubble_sort_f32(len: usize, array: *mut f32);
+}
+extern "C" {
+    #[doc = " Selection sort\n\n # Example\n\n int array[] = {1, 3, 5, 0, 3, 4, 4, 8};\n selection_sort_i32(8, array);\n // array = {0, 1, 3, 3, 4, 4, 5, 8}"]
+    pub fn selection_sort_f32(len: usize, array: *mut f32);
+}
+extern "C" {
+    #[doc = " Insertion sort\n\n # Example\n\n int array[] = {1, 3, 5, 0, 3, 4, 4, 8};\n insertion_sort_i32(8, array);\n // array = {0, 1, 3, 3, 4, 4, 5, 8}"]
+    pub fn insertion_sort_f32(len: usize, array: *mut f32);
+}
+extern "C" {
+    #[doc = " Merge sort\n\n `buffer` is scratch space of at least `len` elements,\n or NULL to allocate it on the heap.\n\n # Example\n\n int array[] = {1, 3, 5, 0, 3, 4, 4, 8};\n merge_sort_i32(8, array, NULL);\n // array = {0, 1, 3, 3, 4, 4, 5, 8}"]
+    pub fn merge_sort_f32(len: usize, array: *mut f32, buffer: *mut f32) -> SortError;
+}
+extern "C" {
+    #[doc = " Merge sort parallel.\n\n `buffer` is as for `merge_sort`."]
+    pub fn merge_sort_parallel_f32(len: usize, array: *mut f32, buffer: *mut f32) -> SortError;
+}
+extern "C" {
+    #[doc = " c std qsort"]
+    pub fn std_qsort_f32(len: usize, array: *mut f32);
+}
+extern "C" {
+    #[doc = " Quick sort"]
+    pub fn quick_sort_f32(len: usize, array: *mut f32);
+}
+extern "C" {
+    #[doc = " Radix LSD Sort\n\n Sort the keys of the elements (see sort.h) with base 256,\n the number of keys is KEY_BYTES, see each-type.h\n radix_lsd_sort_with(len, keys[len], 256, KEY_BYTES, buffer);\n\n `buffer` is scratch space of at least `len` nodes,\n or NULL to allocate it on the heap.\n\n # Example\n\n int array[] = {1, 3, 5, 0, 3, 4, 4, 8};\n radix_lsd_sort_i32(8, array, NULL);\n // array = {0, 1, 3, 3, 4, 4, 5, 8}"]
+    pub fn radix_lsd_sort_f32(len: usize, array: *mut f32, buffer: *mut LinkNode) -> SortError;
+}
+extern "C" {
+    #[doc = " Merge two sorted array into one sorted array\n\n `buffer` is scratch space of at least `len` elements,\n or NULL to allocate it on the heap.\n\n Return `SORT_BAD_SPLIT` if `split > len`.\n\n # Example\n\n int array[] = {1, 3, 5, 0, 3, 4, 4, 8};\n //                     ^split        ^len\n merge_two_sorted_array_i32(8, array, 3, NULL);\n // array = {0, 1, 3, 3, 4, 4, 5, 8}"]
+    pub fn merge_two_sorted_array_f64(
+        len: usize,
+        array: *mut f64,
+        split: usize,
+        buffer: *mut f64,
+    ) -> SortError;
+}
+extern "C" {
+    #[doc = " Bubble sort\n\n # Example\n\n int array[] = {1, 3, 5, 0, 3, 4, 4, 8};\n bubble_sort_i32(8, array);\n // array = {0, 1, 3, 3, 4, 4, 5, 8}"]
+    pub fn bubble_sort_f64(len: usize, array: *mut f64);
+}
+extern "C" {
+    #[doc = " Selection sort\n\n # Example\n\n int array[] = {1, 3, 5, 0, 3, 4, 4, 8};\n selection_sort_i32(8, array);\n // array = {0, 1, 3, 3, 4, 4, 5, 8}"]
+    pub fn selection_sort_f64(len: usize, array: *mut f64);
+}
+extern "C" {
+    #[doc = " Insertion sort\n\n # Example\n\n int array[] = {1, 3, 5, 0, 3, 4, 4, 8};\n insertion_sort_i32(8, array);\n // array = {0, 1, 3, 3, 4, 4, 5, 8}"]
+    pub fn insertion_sort_f64(len: usize, array: *mut f64);
+}
+extern "C" {
+    #[doc = " Merge sort\n\n `buffer` is scratch space of at least `len` elements,\n or NULL to allocate it on the heap.\n\n # Example\n\n int array[] = {1, 3, 5, 0, 3, 4, 4, 8};\n merge_sort_i32(8, array, NULL);\n // array = {0, 1, 3, 3, 4, 4, 5, 8}"]
+    pub fn merge_sort_f64(len: usize, array: *mut f64, buffer: *mut f64) -> SortError;
+}
+extern "C" {
+    #[doc = " Merge sort parallel.\n\n `buffer` is as for `merge_sort`."]
+    pub fn merge_sort_parallel_f64(len: usize, array: *mut f64, buffer: *mut f64) -> SortError;
+}
+extern "C" {
+    #[doc = " c std qsort"]
+    pub fn std_qsort_f64(len: usize, array: *mut f64);
+}
+extern "C" {
+    #[doc = " Quick sort"]
+    pub fn quick_sort_f64(len: usize, array: *mut f64);
+}
+extern "C" {
+    #[doc = " Radix LSD Sort\n\n Sort the keys of the elements (see sort.h) with base 256,\n the number of keys is KEY_BYTES, see each-type.h\n radix_lsd_sort_with(len, keys[len], 256, KEY_BYTES, buffer);\n\n `buffer` is scratch space of at least `len` nodes,\n or NULL to allocate it on the heap.\n\n # Example\n\n int array[] = {1, 3, 5, 0, 3, 4, 4, 8};\n radix_lsd_sort_i32(8, array, NULL);\n // array = {0, 1, 3, 3, 4, 4, 5, 8}"]
+    pub fn radix_lsd_sort_f64(len: usize, array: *mut f64, buffer: *mut LinkNode) -> SortError;
+}
+extern "C" {
+    #[doc = " Merge two sorted array into one sorted array\n\n `buffer` is scratch space of at least `len` elements,\n or NULL to allocate it on the heap.\n\n Return `SORT_BAD_SPLIT` if `split > len`.\n\n # Example\n\n int array[] = {1, 3, 5, 0, 3, 4, 4, 8};\n //                     ^split        ^len\n merge_two_sorted_array_i32(8, array, 3, NULL);\n // array = {0, 1, 3, 3, 4, 4, 5, 8}"]
+    pub fn merge_two_sorted_array_keyed(
+        len: usize,
+        array: *mut uint64_t,
+        split: usize,
+        buffer: *mut uint64_t,
+    ) -> SortError;
+}
+extern "C" {
+    #[doc = " Bubble sort\n\n # Example\n\n int array[] = {1, 3, 5, 0, 3, 4, 4, 8};\n bubble_sort_i32(8, array);\n // array = {0, 1, 3, 3, 4, 4, 5, 8}"]
+    pub fn bubble_sort_keyed(len: usize, array: *mut uint64_t);
+}
+extern "C" {
+    #[doc = " Selection sort\n\n # Example\n\n int array[] = {1, 3, 5, 0, 3, 4, 4, 8};\n selection_sort_i32(8, array);\n // array = {0, 1, 3, 3, 4, 4, 5, 8}"]
+    pub fn selection_sort_keyed(len: usize, array: *mut uint64_t);
+}
+extern "C" {
+    #[doc = " Insertion sort\n\n # Example\n\n int array[] = {1, 3, 5, 0, 3, 4, 4, 8};\n insertion_sort_i32(8, array);\n // array = {0, 1, 3, 3, 4, 4, 5, 8}"]
+    pub fn insertion_sort_keyed(len: usize, array: *mut uint64_t);
+}
+extern "C" {
+    #[doc = " Merge sort\n\n `buffer` is scratch space of at least `len` elements,\n or NULL to allocate it on the heap.\n\n # Example\n\n int array[] = {1, 3, 5, 0, 3, 4, 4, 8};\n merge_sort_i32(8, array, NULL);\n // array = {0, 1, 3, 3, 4, 4, 5, 8}"]
+    pub fn merge_sort_keyed(len: usize, array: *mut uint64_t, buffer: *mut uint64_t) -> SortError;
+}
+extern "C" {
+    #[doc = " Merge sort parallel.\n\n `buffer` is as for `merge_sort`."]
+    pub fn merge_sort_parallel_keyed(
+        len: usize,
+        array: *mut uint64_t,
+        buffer: *mut uint64_t,
+    ) -> SortError;
+}
+extern "C" {
+    #[doc = " c std qsort"]
+    pub fn std_qsort_keyed(len: usize, array: *mut uint64_t);
+}
+extern "C" {
+    #[doc = " Quick sort"]
+    pub fn quick_sort_keyed(len: usize, array: *mut uint64_t);
+}
+extern "C" {
+    #[doc = " Radix LSD Sort\n\n Sort the keys of the elements (see sort.h) with base 256,\n the number of keys is KEY_BYTES, see each-type.h\n radix_lsd_sort_with(len, keys[len], 256, KEY_BYTES, buffer);\n\n `buffer` is scratch space of at least `len` nodes,\n or NULL to allocate it on the heap.\n\n # Example\n\n int array[] = {1, 3, 5, 0, 3, 4, 4, 8};\n radix_lsd_sort_i32(8, array, NULL);\n // array = {0, 1, 3, 3, 4, 4, 5, 8}"]
+    pub fn radix_lsd_sort_keyed(
+        len: usize,
+        array: *mut uint64_t,
+        buffer: *mut LinkNode,
+    ) -> SortError;
+}
 extern "C" {
-    #[doc = " Merge two sorted array into one sorted array\n\n # Example\n\n int array[] = {1, 3, 5, 0, 3, 4, 4, 8};\n //                     ^split        ^len\n merge_two_sorted_array(8, array, 3);\n // array = {0, 1, 3, 3, 4, 4, 5, 8}"]
-    pub fn merge_two_sorted_array(
-        len: ::std::os::raw::c_uint,
-        array: *mut ::std::os::raw::c_int,
-        split: ::std::os::raw::c_uint,
-    );
+    #[doc = " Merge two sorted array into one sorted array\n\n `buffer` is scratch space of at least `len` elements,\n or NULL to allocate it on the heap.\n\n Return `SORT_BAD_SPLIT` if `split > len`.\n\n # Example\n\n int array[] = {1, 3, 5, 0, 3, 4, 4, 8};\n //                     ^split        ^len\n merge_two_sorted_array_i32(8, array, 3, NULL);\n // array = {0, 1, 3, 3, 4, 4, 5, 8}"]
+    pub fn merge_two_sorted_array_counted(
+        len: usize,
+        array: *mut uint64_t,
+        split: usize,
+        buffer: *mut uint64_t,
+    ) -> SortError;
 }
 extern "C" {
-    #[doc = " Bubble sort\n\n # Example\n\n int array[] = {1, 3, 5, 0, 3, 4, 4, 8};\n bubble_sort(8, array);\n // array = {0, 1, 3, 3, 4, 4, 5, 8}"]
-    pub fn bubble_sort(len: ::std::os::raw::c_uint, array: *mut ::std::os::raw::c_int);
+    #[doc = " Bubble sort\n\n # Example\n\n int array[] = {1, 3, 5, 0, 3, 4, 4, 8};\n bubble_sort_i32(8, array);\n // array = {0, 1, 3, 3, 4, 4, 5, 8}"]
+    pub fn bubble_sort_counted(len: usize, array: *mut uint64_t);
 }
 extern "C" {
-    #[doc = " Selection sort\n\n # Example\n\n int array[] = {1, 3, 5, 0, 3, 4, 4, 8};\n selection_sort(8, array);\n // array = {0, 1, 3, 3, 4, 4, 5, 8}"]
-    pub fn selection_sort(len: ::std::os::raw::c_uint, array: *mut ::std::os::raw::c_int);
+    #[doc = " Selection sort\n\n # Example\n\n int array[] = {1, 3, 5, 0, 3, 4, 4, 8};\n selection_sort_i32(8, array);\n // array = {0, 1, 3, 3, 4, 4, 5, 8}"]
+    pub fn selection_sort_counted(len: usize, array: *mut uint64_t);
 }
 extern "C" {
-    #[doc = " Insertion sort\n\n # Example\n\n int array[] = {1, 3, 5, 0, 3, 4, 4, 8};\n insertion_sort(8, array);\n // array = {0, 1, 3, 3, 4, 4, 5, 8}"]
-    pub fn insertion_sort(len: ::std::os::raw::c_uint, array: *mut ::std::os::raw::c_int);
+    #[doc = " Insertion sort\n\n # Example\n\n int array[] = {1, 3, 5, 0, 3, 4, 4, 8};\n insertion_sort_i32(8, array);\n // array = {0, 1, 3, 3, 4, 4, 5, 8}"]
+    pub fn insertion_sort_counted(len: usize, array: *mut uint64_t);
 }
 extern "C" {
-    #[doc = " Merge sort\n\n # Example\n\n int array[] = {1, 3, 5, 0, 3, 4, 4, 8};\n merge_sort(8, array);\n // array = {0, 1, 3, 3, 4, 4, 5, 8}"]
-    pub fn merge_sort(len: ::std::os::raw::c_uint, array: *mut ::std::os::raw::c_int);
+    #[doc = " Merge sort\n\n `buffer` is scratch space of at least `len` elements,\n or NULL to allocate it on the heap.\n\n # Example\n\n int array[] = {1, 3, 5, 0, 3, 4, 4, 8};\n merge_sort_i32(8, array, NULL);\n // array = {0, 1, 3, 3, 4, 4, 5, 8}"]
+    pub fn merge_sort_counted(len: usize, array: *mut uint64_t, buffer: *mut uint64_t)
+        -> SortError;
 }
 extern "C" {
-    #[doc = " Merge sort parallel."]
-    pub fn merge_sort_parallel(len: ::std::os::raw::c_uint, array: *mut ::std::os::raw::c_int);
+    #[doc = " Merge sort parallel.\n\n `buffer` is as for `merge_sort`."]
+    pub fn merge_sort_parallel_counted(
+        len: usize,
+        array: *mut uint64_t,
+        buffer: *mut uint64_t,
+    ) -> SortError;
 }
 extern "C" {
     #[doc = " c std qsort"]
-    pub fn std_qsort(len: ::std::os::raw::c_uint, array: *mut ::std::os::raw::c_int);
+    pub fn std_qsort_counted(len: usize, array: *mut uint64_t);
 }
 extern "C" {
     #[doc = " Quick sort"]
-    pub fn quick_sort(len: ::std::os::raw::c_uint, array: *mut ::std::os::raw::c_int);
+    pub fn quick_sort_counted(len: usize, array: *mut uint64_t);
 }
 extern "C" {
-    #[doc = " Radix LSD Sort\n\n default base is 256, number of keys is 4\n radix_lsd_sort_with(len, array[len], 256, 4);\n\n # Example\n\n int array[] = {1, 3, 5, 0, 3, 4, 4, 8};\n radix_sort(8, array);\n // array = {0, 1, 3, 3, 4, 4, 5, 8}"]
-    pub fn radix_lsd_sort(len: ::std::os::raw::c_uint, array: *mut ::std::os::raw::c_int);
+    #[doc = " Radix LSD Sort\n\n Sort the keys of the elements (see sort.h) with base 256,\n the number of keys is KEY_BYTES, see each-type.h\n radix_lsd_sort_with(len, keys[len], 256, KEY_BYTES, buffer);\n\n `buffer` is scratch space of at least `len` nodes,\n or NULL to allocate it on the heap.\n\n # Example\n\n int array[] = {1, 3, 5, 0, 3, 4, 4, 8};\n radix_lsd_sort_i32(8, array, NULL);\n // array = {0, 1, 3, 3, 4, 4, 5, 8}"]
+    pub fn radix_lsd_sort_counted(
+        len: usize,
+        array: *mut uint64_t,
+        buffer: *mut LinkNode,
+    ) -> SortError;
 }
@@ -0,0 +1,472 @@
+//! Sort files larger than memory.
+//!
+//! The input is read in chunks fitting in `memory_limit`, every chunk is
+//! sorted with a [`SliceSolution`] and spilled to a temporary file as a run.
+//! Runs are then merged `fan_in` at a time, until one merge writes the output.
+//! Each merge is a [`kmerge_by`], so it is stable:
+//! equal records keep the order of their runs.
+//!
+//! Files hold fixed-size little-endian [`Record`]s back to back.
+//!
+//! # Examples
+//!
+//! ```
+//! use sort::external::{read_records, write_records, ExternalSort};
+//! use sort::Stable;
+//! let dir = std::env::temp_dir();
+//! let (input, output) = (dir.join("external-doc-in"), dir.join("external-doc-out"));
+//! write_records(&input, &[5i32, -1, 3, 0, 2])?;
+//!
+//! let external = ExternalSort {
+//!     memory_limit: 8,
+//!     fan_in: 2,
+//!     ..Default::default()
+//! };
+//! let stats = external.sort_file::<i32, _>(Stable, &input, &output)?;
+//! assert_eq!(stats.runs, 3);
+//! assert_eq!(read_records::<i32>(&output)?, [-1, 0, 2, 3, 5]);
+//! # std::fs::remove_file(input)?;
+//! # std::fs::remove_file(output)?;
+//! # Ok::<(), std::io::Error>(())
+//! ```
+
+use crate::merge_k_sorted::kmerge_by;
+use crate::SliceSolution;
+use std::cell::RefCell;
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::marker::PhantomData;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering::Relaxed};
+
+/// Fixed-size element of a file sorted by [`ExternalSort`].
+pub trait Record: Sized {
+    /// Bytes of one record in a file.
+    const SIZE: usize;
+
+    fn read_bytes(bytes: &[u8]) -> Self;
+
+    fn write_bytes(&self, bytes: &mut [u8]);
+}
+
+macro_rules! impl_record {
+    ($($t:ty),*) => {
+        $(
+            impl Record for $t {
+                const SIZE: usize = std::mem::size_of::<$t>();
+
+                fn read_bytes(bytes: &[u8]) -> Self {
+                    <$t>::from_le_bytes(bytes.try_into().unwrap())
+                }
+
+                fn write_bytes(&self, bytes: &mut [u8]) {
+                    bytes.copy_from_slice(&self.to_le_bytes());
+                }
+            }
+        )*
+    };
+}
+
+impl_record!(i32, u32, i64, u64);
+
+#[derive(Debug, Clone)]
+pub struct ExternalSort {
+    /// Bytes of records held in memory, while sorting a chunk or merging.
+    ///
+    /// Records are counted at [`Record::SIZE`] bytes, their size in a file,
+    /// also in the chunk sorted in memory.
+    pub memory_limit: usize,
+    /// Runs merged at once, at least 2.
+    pub fan_in: usize,
+    /// Where the runs are spilled.
+    pub temp_dir: PathBuf,
+}
+
+impl Default for ExternalSort {
+    fn default() -> Self {
+        ExternalSort {
+            memory_limit: 64 << 20,
+            fan_in: 16,
+            temp_dir: std::env::temp_dir(),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ExternalStats {
+    pub records: usize,
+    /// Sorted chunks spilled to temporary files.
+    pub runs: usize,
+    /// Rounds of merges, including the one writing the output.
+    pub passes: usize,
+}
+
+impl ExternalSort {
+    pub fn sort_file<T, S>(
+        &self,
+        solution: S,
+        input: &Path,
+        output: &Path,
+    ) -> io::Result<ExternalStats>
+    where
+        T: Record + Ord,
+        S: SliceSolution<T>,
+    {
+        self.sort_file_by(solution, input, output, T::lt)
+    }
+
+    /// Sort the records of `input` by `is_less` into `output`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `fan_in < 2`.
+    pub fn sort_file_by<T, S, F>(
+        &self,
+        _: S,
+        input: &Path,
+        output: &Path,
+        is_less: F,
+    ) -> io::Result<ExternalStats>
+    where
+        T: Record,
+        S: SliceSolution<T>,
+        F: FnMut(&T, &T) -> bool,
+    {
+        assert!(
+            self.fan_in >= 2,
+            "can not merge {} runs at once",
+            self.fan_in
+        );
+        let mut is_less = is_less;
+        let mut stats = ExternalStats::default();
+
+        let chunk_len = (self.memory_limit / T::SIZE.max(1)).max(1);
+        let mut reader = BufReader::new(File::open(input)?);
+        let mut bytes = vec![0; T::SIZE];
+        let mut chunk = Vec::with_capacity(chunk_len);
+        let mut runs = Vec::new();
+        loop {
+            chunk.clear();
+            while chunk.len() < chunk_len {
+                match read_record(&mut reader, &mut bytes)? {
+                    Some(record) => chunk.push(record),
+                    None => break,
+                }
+            }
+            if chunk.is_empty() {
+                break;
+            }
+
+            stats.records += chunk.len();
+            S::sort_slice_method(&mut chunk, &mut is_less);
+            let run = Run::new(&self.temp_dir);
+            write_records(&run.path, &chunk)?;
+            runs.push(run);
+        }
+        drop(chunk);
+        stats.runs = runs.len();
+
+        while runs.len() > self.fan_in {
+            let mut merged = Vec::new();
+            for group in runs.chunks(self.fan_in) {
+                let run = Run::new(&self.temp_dir);
+                self.merge_runs(group, &run.path, &mut is_less)?;
+                merged.push(run);
+            }
+            // dropping the merged runs removes their files
+            runs = merged;
+            stats.passes += 1;
+        }
+
+        self.merge_runs(&runs, output, &mut is_less)?;
+        stats.passes += 1;
+        Ok(stats)
+    }
+
+    fn merge_runs<T, F>(&self, runs: &[Run], output: &Path, is_less: &mut F) -> io::Result<()>
+    where
+        T: Record,
+        F: FnMut(&T, &T) -> bool,
+    {
+        // the memory limit is shared by the buffers of every run and the output
+        let capacity = (self.memory_limit / (runs.len() + 1)).max(T::SIZE);
+        let error = RefCell::new(None);
+        let readers = runs
+            .iter()
+            .map(|run| {
+                Ok(RunReader {
+                    reader: BufReader::with_capacity(capacity, File::open(&run.path)?),
+                    bytes: vec![0; T::SIZE],
+                    error: &error,
+                    record: PhantomData,
+                })
+            })
+            .collect::<io::Result<Vec<_>>>()?;
+
+        let mut writer = BufWriter::with_capacity(capacity, File::create(output)?);
+        let mut bytes = vec![0; T::SIZE];
+        for record in kmerge_by(readers, is_less) {
+            record.write_bytes(&mut bytes);
+            writer.write_all(&bytes)?;
+        }
+        writer.flush()?;
+
+        match error.into_inner() {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Temporary file of a sorted run, removed on drop.
+struct Run {
+    path: PathBuf,
+}
+
+impl Run {
+    fn new(dir: &Path) -> Self {
+        static COUNT: AtomicUsize = AtomicUsize::new(0);
+        let name = format!(
+            "sort-run-{}-{}",
+            std::process::id(),
+            COUNT.fetch_add(1, Relaxed)
+        );
+        Run {
+            path: dir.join(name),
+        }
+    }
+}
+
+impl Drop for Run {
+    fn drop(&mut self) {
+        _ = std::fs::remove_file(&self.path);
+    }
+}
+
+/// Records of a run, stopping at the first error, which is kept in `error`.
+struct RunReader<'a, T> {
+    reader: BufReader<File>,
+    /// Reused for every record.
+    bytes: Vec<u8>,
+    error: &'a RefCell<Option<io::Error>>,
+    record: PhantomData<T>,
+}
+
+impl<T: Record> Iterator for RunReader<'_, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        read_record(&mut self.reader, &mut self.bytes).unwrap_or_else(|error| {
+            self.error.borrow_mut().get_or_insert(error);
+            None
+        })
+    }
+}
+
+/// `None` at the end of `reader`, an error if it ends inside a record.
+///
+/// `bytes` holds [`Record::SIZE`] bytes, reused between records.
+fn read_record<T: Record>(reader: &mut impl Read, bytes: &mut [u8]) -> io::Result<Option<T>> {
+    debug_assert_eq!(bytes.len(), T::SIZE);
+    let mut filled = 0;
+    while filled < T::SIZE {
+        match reader.read(&mut bytes[filled..])? {
+            0 if filled == 0 => return Ok(None),
+            0 => {
+                return Err(io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    format!("file ends inside a record of {} bytes", T::SIZE),
+                ))
+            }
+            n => filled += n,
+        }
+    }
+    Ok(Some(T::read_bytes(bytes)))
+}
+
+pub fn read_records<T: Record>(path: &Path) -> io::Result<Vec<T>> {
+    let mut reader = BufReader::new(File::open(path)?);
+    let mut bytes = vec![0; T::SIZE];
+    let mut records = Vec::new();
+    while let Some(record) = read_record(&mut reader, &mut bytes)? {
+        records.push(record);
+    }
+    Ok(records)
+}
+
+pub fn write_records<T: Record>(path: &Path, records: &[T]) -> io::Result<()> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    let mut bytes = vec![0; T::SIZE];
+    for record in records {
+        record.write_bytes(&mut bytes);
+        writer.write_all(&bytes)?;
+    }
+    writer.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::bench_data;
+    use crate::{ParallelMerge, Stable};
+
+    /// A 12 bytes record sorted by `key`.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    struct Entry {
+        key: u64,
+        value: u32,
+    }
+
+    impl Record for Entry {
+        const SIZE: usize = 12;
+
+        fn read_bytes(bytes: &[u8]) -> Self {
+            Entry {
+                key: u64::read_bytes(&bytes[..8]),
+                value: u32::read_bytes(&bytes[8..]),
+            }
+        }
+
+        fn write_bytes(&self, bytes: &mut [u8]) {
+            self.key.write_bytes(&mut bytes[..8]);
+            self.value.write_bytes(&mut bytes[8..]);
+        }
+    }
+
+    /// A directory of its own for every test, removed on drop.
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new(name: &str) -> Self {
+            let path =
+                std::env::temp_dir().join(format!("sort-external-{}-{}", name, std::process::id()));
+            std::fs::create_dir_all(&path).unwrap();
+            TempDir(path)
+        }
+
+        /// Files left besides `input` and `output`.
+        fn leftovers(&self) -> usize {
+            std::fs::read_dir(&self.0).unwrap().count() - 2
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            _ = std::fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn external(dir: &TempDir, memory_limit: usize, fan_in: usize) -> ExternalSort {
+        ExternalSort {
+            memory_limit,
+            fan_in,
+            temp_dir: dir.0.clone(),
+        }
+    }
+
+    #[test]
+    fn bench_data_i32() {
+        let dir = TempDir::new("i32");
+        let (input, output) = (dir.0.join("input"), dir.0.join("output"));
+
+        for (name, data) in bench_data() {
+            write_records(&input, &data).unwrap();
+            // 256 records per run, 40 runs for 10000 records
+            let stats = external(&dir, 1024, 4)
+                .sort_file::<i32, _>(Stable, &input, &output)
+                .unwrap();
+
+            let mut expected = data.clone();
+            expected.sort();
+            assert_eq!(read_records::<i32>(&output).unwrap(), expected, "{}", name);
+            assert_eq!(stats.records, data.len());
+            assert_eq!(stats.runs, data.len().div_ceil(256));
+            assert_eq!(dir.leftovers(), 0);
+        }
+    }
+
+    #[test]
+    fn passes() {
+        let dir = TempDir::new("passes");
+        let (input, output) = (dir.0.join("input"), dir.0.join("output"));
+        let data: Vec<u64> = (0..10_000u64)
+            .map(|x| x.wrapping_mul(0x9e37_79b9_7f4a_7c15))
+            .collect();
+        write_records(&input, &data).unwrap();
+
+        let mut expected = data.clone();
+        expected.sort();
+
+        // 100 runs: 100 -> 25 -> 7 -> 2 -> 1
+        let stats = external(&dir, 800, 4)
+            .sort_file::<u64, _>(ParallelMerge, &input, &output)
+            .unwrap();
+        assert_eq!((stats.runs, stats.passes), (100, 4));
+        assert_eq!(read_records::<u64>(&output).unwrap(), expected);
+
+        let stats = external(&dir, 1 << 20, 16)
+            .sort_file::<u64, _>(Stable, &input, &output)
+            .unwrap();
+        assert_eq!((stats.runs, stats.passes), (1, 1));
+        assert_eq!(read_records::<u64>(&output).unwrap(), expected);
+    }
+
+    #[test]
+    fn stable_records() {
+        let dir = TempDir::new("records");
+        let (input, output) = (dir.0.join("input"), dir.0.join("output"));
+        let data: Vec<Entry> = bench_data()[0]
+            .1
+            .iter()
+            .enumerate()
+            .map(|(i, x)| Entry {
+                key: x.div_euclid(100) as u64,
+                value: i as u32,
+            })
+            .collect();
+        write_records(&input, &data).unwrap();
+
+        let stats = external(&dir, Entry::SIZE * 100, 3)
+            .sort_file_by(Stable, &input, &output, |a: &Entry, b: &Entry| {
+                a.key < b.key
+            })
+            .unwrap();
+        assert_eq!(stats.runs, 100);
+
+        let mut expected = data.clone();
+        expected.sort_by_key(|e| e.key);
+        assert_eq!(read_records::<Entry>(&output).unwrap(), expected);
+    }
+
+    #[test]
+    fn empty_file() {
+        let dir = TempDir::new("empty");
+        let (input, output) = (dir.0.join("input"), dir.0.join("output"));
+        write_records::<i32>(&input, &[]).unwrap();
+
+        let stats = ExternalSort::default()
+            .sort_file::<i32, _>(Stable, &input, &output)
+            .unwrap();
+        assert_eq!(
+            stats,
+            ExternalStats {
+                records: 0,
+                runs: 0,
+                passes: 1
+            }
+        );
+        assert!(read_records::<i32>(&output).unwrap().is_empty());
+    }
+
+    #[test]
+    fn truncated_file() {
+        let dir = TempDir::new("truncated");
+        let (input, output) = (dir.0.join("input"), dir.0.join("output"));
+        std::fs::write(&input, [1, 0, 0, 0, 2, 0]).unwrap();
+
+        let error = ExternalSort::default()
+            .sort_file::<i32, _>(Stable, &input, &output)
+            .unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::UnexpectedEof);
+    }
+}
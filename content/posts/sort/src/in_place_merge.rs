@@ -0,0 +1,166 @@
+//! Stable merge sort without a buffer, merging with rotations.
+//!
+//! Blocks are sorted by insertion, then merged bottom-up with
+//! [`sym_merge_by`], which needs only O(log n) stack.
+//! Comparisons stay O(n log n), but moves grow to O(n log^2 n).
+//!
+//! # Examples
+//!
+//! ```
+//! use sort::{InPlaceMerge, SliceSolution};
+//! let mut vec: Vec<i32> = (0..1000).rev().collect();
+//! let sorted = InPlaceMerge::sort_slice(&mut vec);
+//! assert!(sorted.force_check());
+//! ```
+
+use crate::merge_two_sorted::sym_merge_by;
+use crate::select::insertion_sort;
+use crate::{SliceSolution, Solution, Sorted, SortedSlice, StableSolution};
+
+/// Blocks of this length are sorted by insertion before merging.
+const INSERTION: usize = 16;
+
+#[derive(Copy, Clone)]
+pub struct InPlaceMerge;
+
+impl StableSolution for InPlaceMerge {}
+
+impl<T, const N: usize> Solution<T, N> for InPlaceMerge {
+    fn sort_method<F>(array: [T; N], is_less: F) -> Sorted<T, N, impl FnMut(&T, &T) -> bool>
+    where
+        F: FnMut(&T, &T) -> bool,
+    {
+        let mut is_less = is_less;
+        let mut array = array;
+        merge_sort(&mut array, &mut is_less);
+        unsafe { Sorted::uncheck_from_array(array, is_less) }
+    }
+}
+
+impl<T> SliceSolution<T> for InPlaceMerge {
+    fn sort_slice_method<F>(
+        slice: &mut [T],
+        is_less: F,
+    ) -> SortedSlice<'_, T, impl FnMut(&T, &T) -> bool>
+    where
+        F: FnMut(&T, &T) -> bool,
+    {
+        let mut is_less = is_less;
+        merge_sort(slice, &mut is_less);
+        unsafe { SortedSlice::uncheck_from_slice(slice, is_less) }
+    }
+}
+
+fn merge_sort<T, F>(slice: &mut [T], is_less: &mut F)
+where
+    F: FnMut(&T, &T) -> bool,
+{
+    slice
+        .chunks_mut(INSERTION)
+        .for_each(|block| insertion_sort(block, is_less));
+
+    let mut width = INSERTION;
+    while width < slice.len() {
+        for pair in slice.chunks_mut(2 * width) {
+            if pair.len() > width {
+                sym_merge_by(pair, width, &mut *is_less);
+            }
+        }
+        width *= 2;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::*;
+
+    #[test]
+    fn simple() {
+        let array = [5, 3, 9, 0, 1, 8, 2, 7, 4, 6];
+        let a = InPlaceMerge::sort_method(array, |a, b| a < b);
+        assert!(a.force_check());
+        assert_eq!(a.take(), [0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+    }
+
+    #[test]
+    fn empty() {
+        test_empty(InPlaceMerge);
+    }
+
+    #[test]
+    fn random() {
+        test_random(InPlaceMerge);
+    }
+
+    #[test]
+    fn stroll() {
+        test_stroll(InPlaceMerge);
+    }
+
+    #[test]
+    fn gaussian_with_noise() {
+        test_gaussian_with_noise(InPlaceMerge);
+    }
+
+    #[test]
+    fn not_copy() {
+        let mut vec: Vec<String> = (0..1000).rev().map(|x| x.to_string()).collect();
+        let mut expected = vec.clone();
+        expected.sort();
+        let sorted = InPlaceMerge::sort_slice(&mut vec);
+        assert_eq!(*sorted, expected);
+    }
+
+    mod stability {
+        use super::*;
+
+        #[test]
+        fn random() {
+            test_stable_random(InPlaceMerge);
+        }
+
+        #[test]
+        fn stroll() {
+            test_stable_stroll(InPlaceMerge);
+        }
+
+        #[test]
+        fn gaussian_with_noise() {
+            test_stable_gaussian_with_noise(InPlaceMerge);
+        }
+    }
+
+    mod slice {
+        use super::*;
+
+        #[test]
+        fn random() {
+            test_slice_random(InPlaceMerge);
+        }
+
+        #[test]
+        fn trend_increasing() {
+            test_slice_trend_increasing(InPlaceMerge);
+        }
+
+        #[test]
+        fn high_sample_sin_with_noise() {
+            test_slice_high_sample_sin_with_noise(InPlaceMerge);
+        }
+
+        mod stability {
+            use super::*;
+
+            #[test]
+            fn random() {
+                test_slice_stable_random(InPlaceMerge);
+            }
+
+            #[test]
+            fn stroll() {
+                test_slice_stable_stroll(InPlaceMerge);
+            }
+        }
+    }
+}
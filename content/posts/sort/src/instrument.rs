@@ -0,0 +1,322 @@
+//! Count what a sort does instead of only timing it.
+//!
+//! Comparisons are counted by wrapping `is_less`, or by the C sorts themselves.
+//! [`Tracked`] elements count their clones. Rust moves elements bitwise
+//! (`swap`, `ptr::copy`), which can not be observed, so writes and swaps are
+//! only counted by the C sorts, in `SWAP` and their assignments.
+//! Every element also remembers its origin, and moves are bounded below
+//! by the number of displaced elements.
+//!
+//! # Examples
+//!
+//! ```
+//! use sort::instrument::measure;
+//! use sort::Stable;
+//! let stats = measure(Stable, &[3, 1, 2]);
+//! assert_eq!(stats.len, 3);
+//! assert_eq!(stats.displaced, 3);
+//! assert!(stats.comparisons >= 2);
+//! assert_eq!(stats.clones, Some(0));
+//! assert_eq!(stats.writes, None);
+//! ```
+
+use crate::csort::{self, Counted};
+use crate::SliceSolution;
+use serde::Serialize;
+use std::cell::Cell;
+use std::cmp::Ordering;
+use std::sync::atomic::{AtomicUsize, Ordering::Relaxed};
+use std::sync::{Mutex, PoisonError};
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct SortStats {
+    pub len: usize,
+    /// Calls of `is_less`, or of `LESS` in C.
+    pub comparisons: usize,
+    /// Calls of `Tracked::clone`, `None` for C, which copies by assignment.
+    pub clones: Option<usize>,
+    /// Elements stored to the array or a buffer by C, two for every swap.
+    /// `None` for Rust and for `qsort` of the C library.
+    pub writes: Option<usize>,
+    /// Calls of `SWAP` in C, `None` where writes are `None`.
+    pub swaps: Option<usize>,
+    /// Elements not in their original position after the sort,
+    /// so every sort moves at least this many elements.
+    pub displaced: usize,
+}
+
+/// Element wrapper counting its clones and remembering its origin.
+///
+/// Compares by `value` only. The clones of all threads go to one counter
+/// rather than a field, which would change the size of the elements
+/// and with it the strategy of the standard sorts.
+#[derive(Debug)]
+pub struct Tracked<T> {
+    pub value: T,
+    pub origin: usize,
+}
+
+static TRACKED_CLONES: AtomicUsize = AtomicUsize::new(0);
+
+impl<T: Clone> Clone for Tracked<T> {
+    fn clone(&self) -> Self {
+        TRACKED_CLONES.fetch_add(1, Relaxed);
+        Tracked {
+            value: self.value.clone(),
+            origin: self.origin,
+        }
+    }
+}
+
+impl<T: PartialEq> PartialEq for Tracked<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl<T: Eq> Eq for Tracked<T> {}
+
+impl<T: PartialOrd> PartialOrd for Tracked<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.value.partial_cmp(&other.value)
+    }
+}
+
+impl<T: Ord> Ord for Tracked<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.value.cmp(&other.value)
+    }
+}
+
+/// Wrap `is_less`, adding one to `counter` for every call.
+///
+/// # Examples
+///
+/// ```
+/// use sort::instrument::counting;
+/// use sort::{Solution, Stable};
+/// use std::cell::Cell;
+/// let counter = Cell::new(0);
+/// let sorted = Stable::sort_method([2, 1], counting(i32::lt, &counter)).take();
+/// assert_eq!(sorted, [1, 2]);
+/// assert_eq!(counter.get(), 1);
+/// ```
+pub fn counting<'a, T, F>(is_less: F, counter: &'a Cell<usize>) -> impl FnMut(&T, &T) -> bool + 'a
+where
+    F: FnMut(&T, &T) -> bool + 'a,
+{
+    let mut is_less = is_less;
+    move |a, b| {
+        counter.set(counter.get() + 1);
+        is_less(a, b)
+    }
+}
+
+/// Sort a copy of `data` with `solution` and count its work.
+pub fn measure<T, S>(_: S, data: &[T]) -> SortStats
+where
+    T: Ord + Clone,
+    S: SliceSolution<Tracked<T>>,
+{
+    let mut tracked: Vec<_> = data
+        .iter()
+        .enumerate()
+        .map(|(origin, value)| Tracked {
+            value: value.clone(),
+            origin,
+        })
+        .collect();
+
+    let comparisons = Cell::new(0);
+    let clones = {
+        let _lock = COUNTERS.lock().unwrap_or_else(PoisonError::into_inner);
+        TRACKED_CLONES.store(0, Relaxed);
+        S::sort_slice_method(&mut tracked, counting(Tracked::lt, &comparisons));
+        TRACKED_CLONES.swap(0, Relaxed)
+    };
+
+    SortStats {
+        len: data.len(),
+        comparisons: comparisons.get(),
+        clones: Some(clones),
+        writes: None,
+        swaps: None,
+        displaced: displaced(tracked.iter().map(|x| x.origin)),
+    }
+}
+
+/// The clones of [`Tracked`] and the work of the C sorts are counted
+/// for all threads together, so only one sort is measured at a time.
+static COUNTERS: Mutex<()> = Mutex::new(());
+
+/// Sort a copy of `data` with a C sort and count its work.
+pub(crate) fn measure_c(sort: fn(&mut [Counted]), data: &[i32]) -> SortStats {
+    let mut counted: Vec<_> = data
+        .iter()
+        .zip(0..)
+        .map(|(&value, origin)| Counted::new(value, origin))
+        .collect();
+
+    let (comparisons, writes, swaps) = {
+        let _lock = COUNTERS.lock().unwrap_or_else(PoisonError::into_inner);
+        take_c_counters();
+        sort(&mut counted);
+        take_c_counters()
+    };
+
+    SortStats {
+        len: data.len(),
+        comparisons,
+        clones: None,
+        writes: Some(writes),
+        swaps: Some(swaps),
+        displaced: displaced(counted.iter().map(|x| x.origin())),
+    }
+}
+
+fn take_c_counters() -> (usize, usize, usize) {
+    (
+        csort::take_comparisons(),
+        csort::take_writes(),
+        csort::take_swaps(),
+    )
+}
+
+fn displaced(origins: impl Iterator<Item = usize>) -> usize {
+    origins
+        .enumerate()
+        .filter(|(i, origin)| i != origin)
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::{Complexity, ALGORITHMS};
+    use crate::test_utils::bench_data;
+    use crate::Stable;
+
+    /// Upper bound of comparisons for each complexity class,
+    /// with a constant factor of 2 for the linearithmic sorts.
+    fn max_comparisons(n: usize, complexity: Complexity) -> usize {
+        let n_log2_n = n as f64 * (n.max(1) as f64).log2();
+        match complexity {
+            Complexity::Linear => n,
+            Complexity::Linearithmic => (2.0 * n_log2_n).ceil() as usize,
+            Complexity::LogSquared => (n_log2_n * (n.max(1) as f64).log2()).ceil() as usize,
+            Complexity::Quadratic => n * n.saturating_sub(1) / 2,
+        }
+    }
+
+    #[test]
+    fn empty() {
+        let stats = SortStats {
+            clones: Some(0),
+            ..SortStats::default()
+        };
+        assert_eq!(measure(Stable, &[] as &[i32]), stats);
+    }
+
+    #[test]
+    fn clones() {
+        #[derive(Copy, Clone)]
+        struct Cloning;
+
+        impl<T: Clone> SliceSolution<T> for Cloning {
+            fn sort_slice_method<F>(
+                slice: &mut [T],
+                is_less: F,
+            ) -> crate::SortedSlice<'_, T, impl FnMut(&T, &T) -> bool>
+            where
+                F: FnMut(&T, &T) -> bool,
+            {
+                let mut is_less = is_less;
+                let mut vec = slice.to_vec();
+                crate::select::insertion_sort(&mut vec, &mut is_less);
+                slice.clone_from_slice(&vec);
+                unsafe { crate::SortedSlice::uncheck_from_slice(slice, is_less) }
+            }
+        }
+
+        let stats = measure(Cloning, &[3, 1, 2]);
+        assert_eq!(stats.clones, Some(6));
+        assert_eq!(stats.displaced, 3);
+    }
+
+    #[test]
+    fn sorted_input() {
+        let data: Vec<_> = (0..1000).collect();
+        let stats = measure(Stable, &data);
+        assert_eq!(stats.comparisons, 999);
+        assert_eq!(stats.displaced, 0);
+    }
+
+    #[test]
+    fn c_sorts() {
+        let stats = measure_c(csort::bubble_sort, &[3, 1, 2]);
+        assert_eq!(stats.comparisons, 3);
+        assert_eq!((stats.writes, stats.swaps), (Some(4), Some(2)));
+        assert_eq!(stats.displaced, 3);
+
+        let data: Vec<_> = (0..1000).collect();
+        let stats = measure_c(csort::insertion_sort, &data);
+        assert_eq!(stats.comparisons, 999);
+        // every element is put back where it was taken from
+        assert_eq!((stats.writes, stats.swaps), (Some(1000), Some(0)));
+        assert_eq!(stats.displaced, 0);
+
+        // pushed to the buffer, moved to the end, drained back
+        let stats = measure_c(|array| csort::merge_sort(array).unwrap(), &[2, 1]);
+        assert_eq!((stats.writes, stats.swaps), (Some(3), Some(0)));
+
+        let stats = measure_c(|array| csort::radix_lsd_sort(array).unwrap(), &[3, 1, 2]);
+        assert_eq!(stats.comparisons, 0);
+        assert_eq!((stats.writes, stats.swaps), (Some(6), Some(0)));
+    }
+
+    #[test]
+    fn counted() {
+        let mut counted = [i32::MAX, -1, 0, i32::MIN].map(|x| Counted::new(x, 7));
+        // the comparisons still go to the counters of the measured sorts
+        let _lock = COUNTERS.lock().unwrap_or_else(PoisonError::into_inner);
+        csort::quick_sort(&mut counted);
+        assert_eq!(counted.map(Counted::value), [i32::MIN, -1, 0, i32::MAX]);
+        assert!(counted.iter().all(|x| x.origin() == 7));
+    }
+
+    #[test]
+    fn bench_data_report() {
+        for (name, data) in bench_data() {
+            for algorithm in ALGORITHMS {
+                let Some(measure) = algorithm.measure else {
+                    continue;
+                };
+                let stats = measure(&data);
+                let max = max_comparisons(data.len(), algorithm.complexity);
+                // radix sort compares nothing
+                let min = match algorithm.complexity {
+                    Complexity::Linear => 0,
+                    _ => data.len() - 1,
+                };
+
+                assert_eq!(stats.len, data.len(), "{} on {}", algorithm.name, name);
+                assert!(
+                    (min..=max).contains(&stats.comparisons),
+                    "{} on {}: {:?}",
+                    algorithm.name,
+                    name,
+                    stats
+                );
+                if let (Some(writes), Some(swaps)) = (stats.writes, stats.swaps) {
+                    assert!(
+                        writes >= stats.displaced && writes >= 2 * swaps,
+                        "{} on {}: {:?}",
+                        algorithm.name,
+                        name,
+                        stats
+                    );
+                }
+            }
+        }
+    }
+}
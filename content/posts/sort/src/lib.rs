@@ -1,5 +1,4 @@
 #![feature(generic_const_exprs)]
-#![feature(concat_idents)]
 #![feature(test)]
 #![allow(dead_code)]
 
@@ -13,10 +12,48 @@ pub mod csort;
 
 pub mod merge_two_sorted;
 
+pub mod merge_k_sorted;
+
+pub mod registry;
+
+pub mod instrument;
+
+pub mod trace;
+
+pub mod select;
+
+pub mod adaptive;
+
+pub mod metrics;
+
+pub mod compare;
+
+pub mod external;
+
+mod query;
+
+mod set_ops;
+
+mod verify;
+
+mod sorted_vec;
+
+mod sorted_serde;
+
+pub use verify::{verify_by, Unsorted};
+
 mod stable;
 
 pub use stable::Stable;
 
+mod parallel_merge;
+
+pub use parallel_merge::ParallelMerge;
+
+mod in_place_merge;
+
+pub use in_place_merge::InPlaceMerge;
+
 use std::cmp::{Ord, Ordering};
 
 pub trait Solution<T, const N: usize>: Copy + Clone {
@@ -45,6 +82,324 @@ pub trait Solution<T, const N: usize>: Copy + Clone {
     {
         Self::sort_method(array, move |a, b| key(a) < key(b))
     }
+
+    /// Sort by `primary`, and by `secondary` between equal `primary` keys
+    ///
+    /// Sorts twice, first by `secondary` and then by `primary`,
+    /// which is only correct for a [`StableSolution`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sort::{Solution, Stable};
+    /// let array = [(2, 'b'), (1, 'b'), (2, 'a'), (1, 'a')];
+    /// let sorted = Stable::sort_by_key_then(array, |x| x.0, |x| x.1);
+    /// assert_eq!(*sorted, [(1, 'a'), (1, 'b'), (2, 'a'), (2, 'b')]);
+    /// ```
+    fn sort_by_key_then<F, K, G, L>(
+        array: [T; N],
+        primary: F,
+        secondary: G,
+    ) -> Sorted<T, N, impl FnMut(&T, &T) -> bool>
+    where
+        Self: StableSolution,
+        F: FnMut(&T) -> K,
+        K: Ord,
+        G: FnMut(&T) -> L,
+        L: Ord,
+    {
+        let mut primary = primary;
+        let mut secondary = secondary;
+        let array = Self::sort_by_key(array, &mut secondary).take();
+        let array = Self::sort_by_key(array, &mut primary).take();
+        let rule_is_less =
+            move |a: &T, b: &T| (primary(a), secondary(a)) < (primary(b), secondary(b));
+        unsafe { Sorted::uncheck_from_array(array, rule_is_less) }
+    }
+
+    /// Sort by `key`, calling it only once per element.
+    ///
+    /// `(key, index)` pairs are sorted with this solution and the permutation
+    /// is applied to `array`, so the result is stable for any solution.
+    /// The stored rule still compares `key(a) < key(b)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sort::{Solution, Stable};
+    /// let array = ["b", "C", "a"];
+    /// let sorted = Stable::sort_by_cached_key(array, |s| s.to_lowercase());
+    /// assert_eq!(*sorted, ["a", "b", "C"]);
+    /// ```
+    fn sort_by_cached_key<F, K>(array: [T; N], key: F) -> Sorted<T, N, impl FnMut(&T, &T) -> bool>
+    where
+        Self: Solution<(K, usize), N>,
+        F: FnMut(&T) -> K,
+        K: Ord,
+    {
+        let mut key = key;
+        let mut index = 0;
+        let pairs = array.each_ref().map(|x| {
+            index += 1;
+            (key(x), index - 1)
+        });
+        let pairs = <Self as Solution<(K, usize), N>>::sort(pairs).take();
+
+        let mut slots = array.map(Some);
+        let array = pairs.map(|(_, index)| slots[index].take().unwrap());
+        unsafe { Sorted::uncheck_from_array(array, move |a: &T, b: &T| key(a) < key(b)) }
+    }
+
+    /// Sort only the `K` smallest elements, dropping the others.
+    ///
+    /// Selects with [`select::select_nth_unstable_by`] first,
+    /// so only `K` elements are sorted by the solution.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sort::{Solution, Stable};
+    /// let sorted = Stable::partial_sort::<3>([5, 1, 4, 2, 3, 0]);
+    /// assert_eq!(*sorted, [0, 1, 2]);
+    /// ```
+    fn partial_sort_method<const K: usize, F>(
+        array: [T; N],
+        is_less: F,
+    ) -> Sorted<T, K, impl FnMut(&T, &T) -> bool>
+    where
+        Self: Solution<T, K>,
+        F: FnMut(&T, &T) -> bool,
+    {
+        assert!(K <= N, "can not take {} of {} elements", K, N);
+        let mut is_less = is_less;
+        let mut array = array;
+        if K < N {
+            select::select_nth_unstable_by(&mut array, K, &mut is_less);
+        }
+
+        let mut iter = array.into_iter();
+        let prefix = std::array::from_fn(|_| iter.next().unwrap());
+        <Self as Solution<T, K>>::sort_method(prefix, is_less)
+    }
+
+    fn partial_sort<const K: usize>(array: [T; N]) -> Sorted<T, K, impl FnMut(&T, &T) -> bool>
+    where
+        Self: Solution<T, K>,
+        T: Ord,
+    {
+        Self::partial_sort_method(array, T::lt)
+    }
+
+    /// The `K` greatest elements, greatest first.
+    ///
+    /// The result is sorted by the reversed `is_less`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sort::{Solution, Stable};
+    /// let top = Stable::top_k::<2>([5, 1, 4, 2, 3, 0]);
+    /// assert_eq!(*top, [5, 4]);
+    /// ```
+    fn top_k_method<const K: usize, F>(
+        array: [T; N],
+        is_less: F,
+    ) -> Sorted<T, K, impl FnMut(&T, &T) -> bool>
+    where
+        Self: Solution<T, K>,
+        F: FnMut(&T, &T) -> bool,
+    {
+        let mut is_less = is_less;
+        Self::partial_sort_method(array, move |a, b| is_less(b, a))
+    }
+
+    fn top_k<const K: usize>(array: [T; N]) -> Sorted<T, K, impl FnMut(&T, &T) -> bool>
+    where
+        Self: Solution<T, K>,
+        T: Ord,
+    {
+        Self::top_k_method(array, T::lt)
+    }
+}
+
+/// Marker for solutions keeping equal elements in their original order.
+///
+/// Only implement it for solutions passing the stability tests
+/// (`test_stable_*` in `test_utils`, which take any solution).
+///
+/// Methods relying on stability, like [`Solution::sort_by_key_then`],
+/// are not available to other solutions.
+///
+/// ```compile_fail
+/// use sort::adaptive::Adaptive;
+/// use sort::SliceSolution;
+/// let mut vec: Vec<i32> = vec![3, -1, 1, -3];
+/// Adaptive::sort_slice_by_key_then(&mut vec, |x| x.abs(), |x| *x);
+/// ```
+pub trait StableSolution: Copy + Clone {}
+
+pub trait SliceSolution<T>: Copy + Clone {
+    fn sort_slice_method<F>(
+        slice: &mut [T],
+        is_less: F,
+    ) -> SortedSlice<'_, T, impl FnMut(&T, &T) -> bool>
+    where
+        F: FnMut(&T, &T) -> bool;
+
+    fn sort_slice(slice: &mut [T]) -> SortedSlice<'_, T, impl FnMut(&T, &T) -> bool>
+    where
+        T: Ord,
+    {
+        Self::sort_slice_method(slice, T::lt)
+    }
+
+    fn sort_slice_by<F>(
+        slice: &mut [T],
+        mut compare: F,
+    ) -> SortedSlice<'_, T, impl FnMut(&T, &T) -> bool>
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        Self::sort_slice_method(slice, move |a, b| compare(a, b) == Ordering::Less)
+    }
+
+    fn sort_slice_by_key<F, K>(
+        slice: &mut [T],
+        mut key: F,
+    ) -> SortedSlice<'_, T, impl FnMut(&T, &T) -> bool>
+    where
+        F: FnMut(&T) -> K,
+        K: Ord,
+    {
+        Self::sort_slice_method(slice, move |a, b| key(a) < key(b))
+    }
+
+    /// See [`Solution::sort_by_key_then`].
+    fn sort_slice_by_key_then<F, K, G, L>(
+        slice: &mut [T],
+        primary: F,
+        secondary: G,
+    ) -> SortedSlice<'_, T, impl FnMut(&T, &T) -> bool>
+    where
+        Self: StableSolution,
+        F: FnMut(&T) -> K,
+        K: Ord,
+        G: FnMut(&T) -> L,
+        L: Ord,
+    {
+        let mut primary = primary;
+        let mut secondary = secondary;
+        Self::sort_slice_by_key(slice, &mut secondary);
+        let slice = Self::sort_slice_by_key(slice, &mut primary).take();
+        let rule_is_less =
+            move |a: &T, b: &T| (primary(a), secondary(a)) < (primary(b), secondary(b));
+        unsafe { SortedSlice::uncheck_from_slice(slice, rule_is_less) }
+    }
+
+    /// See [`Solution::sort_by_cached_key`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sort::{SliceSolution, Stable};
+    /// let mut vec = vec!["b", "C", "a"];
+    /// let sorted = Stable::sort_slice_by_cached_key(&mut vec, |s| s.to_lowercase());
+    /// assert_eq!(*sorted, ["a", "b", "C"]);
+    /// ```
+    fn sort_slice_by_cached_key<F, K>(
+        slice: &mut [T],
+        key: F,
+    ) -> SortedSlice<'_, T, impl FnMut(&T, &T) -> bool>
+    where
+        Self: SliceSolution<(K, usize)>,
+        F: FnMut(&T) -> K,
+        K: Ord,
+    {
+        let mut key = key;
+        let mut pairs: Vec<(K, usize)> = slice.iter().map(&mut key).zip(0..).collect();
+        <Self as SliceSolution<(K, usize)>>::sort_slice(&mut pairs);
+
+        // position `i` takes the element from `pairs[i].1`, which may already
+        // have been swapped away, follow the swaps to where it went
+        for i in 0..slice.len() {
+            let mut index = pairs[i].1;
+            while index < i {
+                index = pairs[index].1;
+            }
+            pairs[i].1 = index;
+            slice.swap(i, index);
+        }
+
+        unsafe { SortedSlice::uncheck_from_slice(slice, move |a: &T, b: &T| key(a) < key(b)) }
+    }
+
+    /// Sort only the `k` smallest elements to the front of `slice`,
+    /// leaving the others after them in unspecified order.
+    ///
+    /// The whole slice is sorted if `k` exceeds its length.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sort::{SliceSolution, Stable};
+    /// let mut vec = vec![5, 1, 4, 2, 3, 0];
+    /// let sorted = Stable::partial_sort_slice(&mut vec, 3);
+    /// assert_eq!(sorted.take(), [0, 1, 2]);
+    /// ```
+    fn partial_sort_slice_method<F>(
+        slice: &mut [T],
+        k: usize,
+        is_less: F,
+    ) -> SortedSlice<'_, T, impl FnMut(&T, &T) -> bool>
+    where
+        F: FnMut(&T, &T) -> bool,
+    {
+        let mut is_less = is_less;
+        let k = k.min(slice.len());
+        if k < slice.len() {
+            select::select_nth_unstable_by(slice, k, &mut is_less);
+        }
+        Self::sort_slice_method(&mut slice[..k], is_less)
+    }
+
+    fn partial_sort_slice(
+        slice: &mut [T],
+        k: usize,
+    ) -> SortedSlice<'_, T, impl FnMut(&T, &T) -> bool>
+    where
+        T: Ord,
+    {
+        Self::partial_sort_slice_method(slice, k, T::lt)
+    }
+
+    /// Sort the `k` greatest elements to the front of `slice`, greatest first.
+    ///
+    /// See [`Solution::top_k_method`].
+    fn top_k_slice_method<F>(
+        slice: &mut [T],
+        k: usize,
+        is_less: F,
+    ) -> SortedSlice<'_, T, impl FnMut(&T, &T) -> bool>
+    where
+        F: FnMut(&T, &T) -> bool,
+    {
+        let mut is_less = is_less;
+        Self::partial_sort_slice_method(slice, k, move |a, b| is_less(b, a))
+    }
+
+    /// # Examples
+    ///
+    /// ```
+    /// use sort::{SliceSolution, Stable};
+    /// let mut vec = vec![5, 1, 4, 2, 3, 0];
+    /// assert_eq!(Stable::top_k_slice(&mut vec, 2).take(), [5, 4]);
+    /// ```
+    fn top_k_slice(slice: &mut [T], k: usize) -> SortedSlice<'_, T, impl FnMut(&T, &T) -> bool>
+    where
+        T: Ord,
+    {
+        Self::top_k_slice_method(slice, k, T::lt)
+    }
 }
 
 use std::cell::RefCell;
@@ -70,9 +425,17 @@ impl<T, const N: usize, F: FnMut(&T, &T) -> bool> Sorted<T, N, F> {
     }
 
     pub fn force_check(&self) -> bool {
-        self.array
-            .windows(2)
-            .all(|w| (self.rule_is_less.borrow_mut())(&w[0], &w[1]))
+        self.verify().is_ok()
+    }
+
+    /// Borrow as a [`SortedSlice`] sharing the stored `rule_is_less`.
+    pub fn as_sorted_slice(&self) -> SortedSlice<'_, T, impl FnMut(&T, &T) -> bool + '_> {
+        let rule_is_less = &self.rule_is_less;
+        unsafe {
+            SortedSlice::uncheck_from_slice(&self.array, move |a: &T, b: &T| {
+                (rule_is_less.borrow_mut())(a, b)
+            })
+        }
     }
 }
 
@@ -103,3 +466,134 @@ where
         &self.array
     }
 }
+
+/// A sorted view borrowed from a slice (or `Vec`) sorted in place.
+///
+/// # Examples
+///
+/// ```
+/// use sort::{SliceSolution, Stable};
+/// let mut vec = vec![3, 1, 2];
+/// let sorted = Stable::sort_slice(&mut vec);
+/// assert_eq!(sorted.take(), &[1, 2, 3]);
+/// ```
+pub struct SortedSlice<'a, T, F: FnMut(&T, &T) -> bool> {
+    slice: &'a [T],
+    rule_is_less: RefCell<F>,
+}
+
+impl<'a, T, F: FnMut(&T, &T) -> bool> SortedSlice<'a, T, F> {
+    pub fn take(self) -> &'a [T] {
+        self.slice
+    }
+
+    /// # Safety
+    ///
+    /// The caller must ensure that the slice is sorted.
+    pub unsafe fn uncheck_from_slice(slice: &'a [T], rule_is_less: F) -> Self {
+        SortedSlice {
+            slice,
+            rule_is_less: RefCell::new(rule_is_less),
+        }
+    }
+
+    pub fn force_check(&self) -> bool {
+        self.verify().is_ok()
+    }
+}
+
+impl<T, F> std::borrow::Borrow<[T]> for SortedSlice<'_, T, F>
+where
+    F: FnMut(&T, &T) -> bool,
+{
+    fn borrow(&self) -> &[T] {
+        self.slice
+    }
+}
+
+impl<T, F> std::convert::AsRef<[T]> for SortedSlice<'_, T, F>
+where
+    F: FnMut(&T, &T) -> bool,
+{
+    fn as_ref(&self) -> &[T] {
+        self.slice
+    }
+}
+
+impl<T, F> std::ops::Deref for SortedSlice<'_, T, F>
+where
+    F: FnMut(&T, &T) -> bool,
+{
+    type Target = [T];
+    fn deref(&self) -> &Self::Target {
+        self.slice
+    }
+}
+
+/// A sorted `Vec` owning its elements, for results whose length is only
+/// known at runtime.
+///
+/// Unlike [`Sorted`] it can grow and shrink while staying sorted,
+/// see `insert`, `remove`, `retain`, `extend` and `drain_range`.
+pub struct SortedVec<T, F: FnMut(&T, &T) -> bool> {
+    vec: Vec<T>,
+    rule_is_less: RefCell<F>,
+}
+
+impl<T, F: FnMut(&T, &T) -> bool> SortedVec<T, F> {
+    pub fn take(self) -> Vec<T> {
+        self.vec
+    }
+
+    /// # Safety
+    ///
+    /// The caller must ensure that the vec is sorted.
+    pub unsafe fn uncheck_from_vec(vec: Vec<T>, rule_is_less: F) -> Self {
+        SortedVec {
+            vec,
+            rule_is_less: RefCell::new(rule_is_less),
+        }
+    }
+
+    pub fn force_check(&self) -> bool {
+        self.verify().is_ok()
+    }
+
+    /// Borrow as a [`SortedSlice`] sharing the stored `rule_is_less`.
+    pub fn as_sorted_slice(&self) -> SortedSlice<'_, T, impl FnMut(&T, &T) -> bool + '_> {
+        let rule_is_less = &self.rule_is_less;
+        unsafe {
+            SortedSlice::uncheck_from_slice(&self.vec, move |a: &T, b: &T| {
+                (rule_is_less.borrow_mut())(a, b)
+            })
+        }
+    }
+}
+
+impl<T, F> std::borrow::Borrow<[T]> for SortedVec<T, F>
+where
+    F: FnMut(&T, &T) -> bool,
+{
+    fn borrow(&self) -> &[T] {
+        &self.vec
+    }
+}
+
+impl<T, F> std::convert::AsRef<[T]> for SortedVec<T, F>
+where
+    F: FnMut(&T, &T) -> bool,
+{
+    fn as_ref(&self) -> &[T] {
+        &self.vec
+    }
+}
+
+impl<T, F> std::ops::Deref for SortedVec<T, F>
+where
+    F: FnMut(&T, &T) -> bool,
+{
+    type Target = [T];
+    fn deref(&self) -> &Self::Target {
+        &self.vec
+    }
+}
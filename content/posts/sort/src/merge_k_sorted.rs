@@ -0,0 +1,235 @@
+//! Merge any number of sorted inputs with a binary heap of their heads.
+//!
+//! Every step pops the least head and pushes the next element of its input,
+//! so merging `n` elements of `k` inputs takes O(n log k) comparisons.
+//! Equal elements come out in the order of their inputs.
+//!
+//! # Examples
+//!
+//! ```
+//! use sort::merge_k_sorted::{kmerge, merge_k_sorted_slices};
+//! let merged: Vec<_> = kmerge([vec![1, 4, 7], vec![2, 5], vec![3, 6, 9]]).collect();
+//! assert_eq!(merged, [1, 2, 3, 4, 5, 6, 7, 9]);
+//!
+//! let merged = merge_k_sorted_slices(&[&[3, 8], &[], &[1, 9]]);
+//! assert_eq!(merged, [1, 3, 8, 9]);
+//! ```
+
+/// Iterator merging sorted iterators, see [`kmerge_by`].
+pub struct KMerge<I: Iterator, F> {
+    iters: Vec<I>,
+    /// Head of every unfinished input with the index of the input.
+    heap: Vec<(I::Item, usize)>,
+    is_less: F,
+}
+
+impl<T, I, F> KMerge<I, F>
+where
+    I: Iterator<Item = T>,
+    F: FnMut(&T, &T) -> bool,
+{
+    /// `a` comes out before `b`, earlier inputs winning ties.
+    fn before(&mut self, a: usize, b: usize) -> bool {
+        let (a, b) = (&self.heap[a], &self.heap[b]);
+        (self.is_less)(&a.0, &b.0) || (!(self.is_less)(&b.0, &a.0) && a.1 < b.1)
+    }
+
+    fn sift_up(&mut self, mut index: usize) {
+        while index > 0 {
+            let parent = (index - 1) / 2;
+            if !self.before(index, parent) {
+                break;
+            }
+            self.heap.swap(index, parent);
+            index = parent;
+        }
+    }
+
+    fn sift_down(&mut self, mut index: usize) {
+        loop {
+            let (left, right) = (2 * index + 1, 2 * index + 2);
+            let mut first = index;
+            if left < self.heap.len() && self.before(left, first) {
+                first = left;
+            }
+            if right < self.heap.len() && self.before(right, first) {
+                first = right;
+            }
+            if first == index {
+                break;
+            }
+            self.heap.swap(index, first);
+            index = first;
+        }
+    }
+}
+
+impl<T, I, F> Iterator for KMerge<I, F>
+where
+    I: Iterator<Item = T>,
+    F: FnMut(&T, &T) -> bool,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.heap.is_empty() {
+            return None;
+        }
+
+        let source = self.heap[0].1;
+        let (least, _) = match self.iters[source].next() {
+            Some(next) => std::mem::replace(&mut self.heap[0], (next, source)),
+            None => self.heap.swap_remove(0),
+        };
+        self.sift_down(0);
+        Some(least)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iters.iter().map(|iter| iter.size_hint()).fold(
+            (self.heap.len(), Some(self.heap.len())),
+            |(min, max), (a_min, a_max)| {
+                let max = max
+                    .zip(a_max)
+                    .and_then(|(max, a_max)| max.checked_add(a_max));
+                (min.saturating_add(a_min), max)
+            },
+        )
+    }
+}
+
+/// Lazily merge iterators sorted by `is_less`.
+///
+/// Elements of earlier iterators come before equal elements of later ones.
+///
+/// # Examples
+///
+/// ```
+/// use sort::merge_k_sorted::kmerge_by;
+/// let inputs = [vec![(1, 'a'), (2, 'a')], vec![(1, 'b')], vec![(0, 'c'), (2, 'c')]];
+/// let merged: Vec<_> = kmerge_by(inputs, |a, b| a.0 < b.0).collect();
+/// assert_eq!(merged, [(0, 'c'), (1, 'a'), (1, 'b'), (2, 'a'), (2, 'c')]);
+/// ```
+pub fn kmerge_by<T, I, F>(iters: impl IntoIterator<Item = I>, is_less: F) -> KMerge<I::IntoIter, F>
+where
+    I: IntoIterator<Item = T>,
+    F: FnMut(&T, &T) -> bool,
+{
+    let mut iters: Vec<_> = iters.into_iter().map(IntoIterator::into_iter).collect();
+    let mut merge = KMerge {
+        heap: Vec::with_capacity(iters.len()),
+        iters: Vec::new(),
+        is_less,
+    };
+
+    for (source, iter) in iters.iter_mut().enumerate() {
+        if let Some(head) = iter.next() {
+            merge.heap.push((head, source));
+            merge.sift_up(merge.heap.len() - 1);
+        }
+    }
+    merge.iters = iters;
+    merge
+}
+
+pub fn kmerge<T, I>(
+    iters: impl IntoIterator<Item = I>,
+) -> KMerge<I::IntoIter, impl FnMut(&T, &T) -> bool>
+where
+    I: IntoIterator<Item = T>,
+    T: Ord,
+{
+    kmerge_by(iters, T::lt)
+}
+
+/// Merge iterators sorted by `is_less` into a `Vec`, see [`kmerge_by`].
+pub fn merge_k_sorted_by<T, I, F>(iters: impl IntoIterator<Item = I>, is_less: F) -> Vec<T>
+where
+    I: IntoIterator<Item = T>,
+    F: FnMut(&T, &T) -> bool,
+{
+    kmerge_by(iters, is_less).collect()
+}
+
+/// Merge slices sorted by `is_less` into a `Vec` of clones.
+pub fn merge_k_sorted_slices_by<T, F>(slices: &[&[T]], is_less: F) -> Vec<T>
+where
+    T: Clone,
+    F: FnMut(&T, &T) -> bool,
+{
+    merge_k_sorted_by(slices.iter().map(|slice| slice.iter().cloned()), is_less)
+}
+
+pub fn merge_k_sorted_slices<T: Ord + Clone>(slices: &[&[T]]) -> Vec<T> {
+    merge_k_sorted_slices_by(slices, T::lt)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::bench_data;
+
+    #[test]
+    fn empty() {
+        assert!(kmerge(Vec::<Vec<i32>>::new()).next().is_none());
+        assert!(merge_k_sorted_slices::<i32>(&[&[], &[]]).is_empty());
+        assert_eq!(merge_k_sorted_slices(&[&[], &[1], &[]]), [1]);
+    }
+
+    #[test]
+    fn bench_data_chunks() {
+        for (name, data) in bench_data() {
+            let mut expected = data.clone();
+            expected.sort();
+
+            for k in [1, 2, 3, 7, 64, 1000] {
+                let mut chunks: Vec<Vec<i32>> = data
+                    .chunks(data.len().div_ceil(k))
+                    .map(<[i32]>::to_vec)
+                    .collect();
+                chunks.iter_mut().for_each(|chunk| chunk.sort());
+
+                let merged = kmerge(chunks.clone());
+                assert_eq!(merged.size_hint(), (data.len(), Some(data.len())));
+                assert_eq!(
+                    merged.collect::<Vec<_>>(),
+                    expected,
+                    "{} in {} chunks",
+                    name,
+                    k
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn stable_across_inputs() {
+        // every input holds the same keys, tagged with the input
+        let inputs: Vec<Vec<(i32, usize)>> = (0..10)
+            .map(|i| (0..100).map(|x| (x / 3, i)).collect())
+            .collect();
+        let merged = merge_k_sorted_by(inputs, |a, b| a.0 < b.0);
+
+        let mut expected: Vec<_> = (0..10)
+            .flat_map(|i| (0..100).map(move |x| (x / 3, i)))
+            .collect();
+        expected.sort_by_key(|r| r.0);
+        assert_eq!(merged, expected);
+    }
+
+    #[test]
+    fn lazy() {
+        let evens = (0..).step_by(2);
+        let odds = (1..).step_by(2);
+        let merged: Vec<i32> = kmerge([evens, odds]).take(6).collect();
+        assert_eq!(merged, [0, 1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn descending_not_copy() {
+        let a = ["pear", "fig"].map(String::from);
+        let b = ["plum", "kiwi", "apple"].map(String::from);
+        let merged = merge_k_sorted_by([a.to_vec(), b.to_vec()], |a, b| a > b);
+        assert_eq!(merged, ["plum", "pear", "kiwi", "fig", "apple"]);
+    }
+}
@@ -1,120 +1,482 @@
-use std::mem::MaybeUninit;
+use crate::csort::CSortError;
+use crate::Sorted;
+use std::iter::{IntoIterator, Iterator, Peekable};
 
-struct Stack<T, const N: usize> {
-    data: [T; N],
-    len: usize,
+/// Merge two sorted arrays in place
+///
+/// # Examples
+///
+/// ```
+/// #![feature(generic_const_exprs)]
+/// use sort::merge_two_sorted::merge_two_sorted_rs;
+/// let array1 = [1, 3, 5];
+/// let array2 = [2, 9];
+/// let array3 = merge_two_sorted_rs(array1, array2);
+/// assert_eq!(&array3, &[1, 2, 3, 5, 9]);
+/// ```
+pub fn merge_two_sorted_rs<T, const N: usize, const M: usize>(a: [T; N], b: [T; M]) -> [T; N + M]
+where
+    T: Ord,
+{
+    merge_two_sorted_by(a, b, T::lt)
+}
+
+/// Iterator merging two sorted iterators, see [`merge_iter_by`].
+pub struct MergeIter<A: Iterator, B: Iterator, F> {
+    a: Peekable<A>,
+    b: Peekable<B>,
+    is_less: F,
 }
 
-impl<T, const N: usize> Stack<T, N> {
-    fn new() -> Self {
-        let data = MaybeUninit::uninit();
-        Self {
-            data: unsafe { data.assume_init() },
-            len: 0,
+impl<T, A, B, F> Iterator for MergeIter<A, B, F>
+where
+    A: Iterator<Item = T>,
+    B: Iterator<Item = T>,
+    F: FnMut(&T, &T) -> bool,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match (self.a.peek(), self.b.peek()) {
+            (Some(x), Some(y)) if (self.is_less)(y, x) => self.b.next(),
+            (Some(_), _) => self.a.next(),
+            (None, _) => self.b.next(),
         }
     }
 
-    fn push(&mut self, element: T) {
-        if self.len == N {
-            panic!("Stack is full");
-        }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (a_min, a_max) = self.a.size_hint();
+        let (b_min, b_max) = self.b.size_hint();
+        let max = a_max.zip(b_max).and_then(|(a, b)| a.checked_add(b));
+        (a_min.saturating_add(b_min), max)
+    }
+}
 
-        self.data[self.len] = element;
-        self.len += 1;
+/// Lazily merge two iterators sorted by `is_less`
+///
+/// Elements of `a` come before equal elements of `b`.
+///
+/// # Examples
+///
+/// ```
+/// use sort::merge_two_sorted::merge_iter_by;
+/// let merged: Vec<_> = merge_iter_by([1, 3, 5], [2, 3, 9], |a, b| a < b).collect();
+/// assert_eq!(merged, [1, 2, 3, 3, 5, 9]);
+/// ```
+pub fn merge_iter_by<T, A, B, F>(a: A, b: B, is_less: F) -> MergeIter<A::IntoIter, B::IntoIter, F>
+where
+    A: IntoIterator<Item = T>,
+    B: IntoIterator<Item = T>,
+    F: FnMut(&T, &T) -> bool,
+{
+    MergeIter {
+        a: a.into_iter().peekable(),
+        b: b.into_iter().peekable(),
+        is_less,
     }
+}
+
+/// Merge two arrays sorted by `is_less`
+///
+/// Elements of `a` come before equal elements of `b`.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(generic_const_exprs)]
+/// use sort::merge_two_sorted::merge_two_sorted_by;
+/// let array1 = [5, 3, 1];
+/// let array2 = [9, 2];
+/// let array3 = merge_two_sorted_by(array1, array2, |a, b| a > b);
+/// assert_eq!(&array3, &[9, 5, 3, 2, 1]);
+/// ```
+pub fn merge_two_sorted_by<T, F, const N: usize, const M: usize>(
+    a: [T; N],
+    b: [T; M],
+    is_less: F,
+) -> [T; N + M]
+where
+    F: FnMut(&T, &T) -> bool,
+{
+    let mut merged = merge_iter_by(a, b, is_less);
+    std::array::from_fn(|_| merged.next().unwrap())
+}
+
+/// Append clones of the elements of `a` and `b` to `out`, sorted by `is_less`.
+///
+/// Elements of `a` come before equal elements of `b`.
+/// `a` and `b` are only read, if `is_less` panics they are unchanged
+/// and `out` holds the elements merged so far.
+///
+/// # Examples
+///
+/// ```
+/// use sort::merge_two_sorted::merge_into_by;
+/// let a = [String::from("pear"), String::from("fig")];
+/// let b = [String::from("kiwi")];
+/// let mut out = vec![String::from("plum")];
+/// merge_into_by(&a, &b, &mut out, |x, y| x > y);
+/// assert_eq!(out, ["plum", "pear", "kiwi", "fig"]);
+/// ```
+pub fn merge_into_by<T, F>(a: &[T], b: &[T], out: &mut Vec<T>, is_less: F)
+where
+    T: Clone,
+    F: FnMut(&T, &T) -> bool,
+{
+    let mut is_less = is_less;
+    out.reserve(a.len() + b.len());
+    out.extend(merge_iter_by(a, b, |x: &&T, y: &&T| is_less(x, y)).cloned());
+}
+
+/// See [`merge_into_by`].
+///
+/// # Examples
+///
+/// ```
+/// use sort::merge_two_sorted::merge_into;
+/// let mut out = Vec::new();
+/// merge_into(&[1, 3], &[2, 3], &mut out);
+/// assert_eq!(out, [1, 2, 3, 3]);
+/// ```
+pub fn merge_into<T: Ord + Clone>(a: &[T], b: &[T], out: &mut Vec<T>) {
+    merge_into_by(a, b, out, T::lt)
+}
 
-    fn pop(&mut self) -> Option<T> {
-        use std::ptr;
-        match self.len {
-            0 => None,
-            _ => unsafe {
-                self.len -= 1;
-                core::hint::assert_unchecked(self.len < N);
-                Some(ptr::read(self.data.as_ptr().add(self.len)))
-            },
+/// Merge slices sorted by `is_less` into a new `Vec`, see [`merge_into_by`].
+pub fn merge_by<T, F>(a: &[T], b: &[T], is_less: F) -> Vec<T>
+where
+    T: Clone,
+    F: FnMut(&T, &T) -> bool,
+{
+    let mut out = Vec::new();
+    merge_into_by(a, b, &mut out, is_less);
+    out
+}
+
+/// Merge slices sorted by `key` into a new `Vec`, see [`merge_into_by`].
+///
+/// # Examples
+///
+/// ```
+/// use sort::merge_two_sorted::merge_by_key;
+/// let merged = merge_by_key(&["a", "ccc"], &["bb"], |s| s.len());
+/// assert_eq!(merged, ["a", "bb", "ccc"]);
+/// ```
+pub fn merge_by_key<T, K, F>(a: &[T], b: &[T], key: F) -> Vec<T>
+where
+    T: Clone,
+    F: FnMut(&T) -> K,
+    K: Ord,
+{
+    let mut key = key;
+    merge_by(a, b, |x, y| key(x) < key(y))
+}
+
+/// Element produced by [`MergeJoin`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Merged<T> {
+    /// Only in the left input.
+    Left(T),
+    /// Only in the right input.
+    Right(T),
+    /// Equal elements taken from both inputs at once.
+    Both(T, T),
+}
+
+/// Iterator pairing equal elements of two sorted iterators,
+/// see [`merge_join_by`].
+pub struct MergeJoin<A: Iterator, B: Iterator, F> {
+    a: Peekable<A>,
+    b: Peekable<B>,
+    is_less: F,
+}
+
+impl<T, A, B, F> Iterator for MergeJoin<A, B, F>
+where
+    A: Iterator<Item = T>,
+    B: Iterator<Item = T>,
+    F: FnMut(&T, &T) -> bool,
+{
+    type Item = Merged<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match (self.a.peek(), self.b.peek()) {
+            (Some(x), Some(y)) => {
+                if (self.is_less)(x, y) {
+                    self.a.next().map(Merged::Left)
+                } else if (self.is_less)(y, x) {
+                    self.b.next().map(Merged::Right)
+                } else {
+                    self.a
+                        .next()
+                        .zip(self.b.next())
+                        .map(|(x, y)| Merged::Both(x, y))
+                }
+            }
+            (Some(_), None) => self.a.next().map(Merged::Left),
+            (None, _) => self.b.next().map(Merged::Right),
         }
     }
+}
 
-    fn take_if_full(self) -> Option<[T; N]> {
-        if self.len == N {
-            Some(self.data)
-        } else {
-            None
-        }
+/// Lazily merge two iterators sorted by `is_less`, pairing equal elements
+///
+/// Each element is paired at most once, so duplicated elements are
+/// matched one to one, like the multiset operations of C++ `std::set_union`.
+///
+/// # Examples
+///
+/// ```
+/// use sort::merge_two_sorted::{merge_join_by, Merged};
+/// let merged: Vec<_> = merge_join_by([1, 3, 3], [3, 4], |a, b| a < b).collect();
+/// assert_eq!(
+///     merged,
+///     [Merged::Left(1), Merged::Both(3, 3), Merged::Left(3), Merged::Right(4)]
+/// );
+/// ```
+pub fn merge_join_by<T, A, B, F>(a: A, b: B, is_less: F) -> MergeJoin<A::IntoIter, B::IntoIter, F>
+where
+    A: IntoIterator<Item = T>,
+    B: IntoIterator<Item = T>,
+    F: FnMut(&T, &T) -> bool,
+{
+    MergeJoin {
+        a: a.into_iter().peekable(),
+        b: b.into_iter().peekable(),
+        is_less,
     }
+}
 
-    unsafe fn take_no_check(self) -> [T; N] {
-        core::hint::assert_unchecked(self.len == N);
-        self.data
+/// Stably sort `slice` by `is_less` unless it is already sorted by it.
+///
+/// The other side of a merge or set operation carries its own rule,
+/// which may not agree with the rule of the result.
+pub(crate) fn ensure_sorted_by<T, F>(slice: &mut [T], is_less: &mut F)
+where
+    F: FnMut(&T, &T) -> bool,
+{
+    if slice.windows(2).any(|w| is_less(&w[1], &w[0])) {
+        slice.sort_by(|a, b| {
+            if is_less(a, b) {
+                std::cmp::Ordering::Less
+            } else if is_less(b, a) {
+                std::cmp::Ordering::Greater
+            } else {
+                std::cmp::Ordering::Equal
+            }
+        });
     }
 }
 
-use std::iter::{IntoIterator, Iterator};
+impl<T, const N: usize, F: FnMut(&T, &T) -> bool> Sorted<T, N, F> {
+    /// Merge with another sorted array without sorting again
+    ///
+    /// The result keeps the `rule_is_less` of `self`.
+    /// `other` may have been sorted by a different rule, so it is checked
+    /// against `rule_is_less` first and stably sorted by it if out of order.
+    /// Elements of `self` come before equal elements of `other`.
+    ///
+    /// Unlike [`crate::Solution::sort_by_key_then`], it is not limited to a
+    /// [`crate::StableSolution`]: a `Sorted` does not record which solution built it,
+    /// and the merge is stable whichever did.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(generic_const_exprs)]
+    /// use sort::{Solution, Stable};
+    /// let a = Stable::sort([5, 1, 3]);
+    /// let b = Stable::sort([9, 2]);
+    /// let merged = a.merge(b);
+    /// assert_eq!(*merged, [1, 2, 3, 5, 9]);
+    /// ```
+    pub fn merge<const M: usize, G>(self, other: Sorted<T, M, G>) -> Sorted<T, { N + M }, F>
+    where
+        G: FnMut(&T, &T) -> bool,
+        [(); N + M]:,
+    {
+        let mut rule_is_less = self.rule_is_less.into_inner();
+        let mut other = other.array;
+        ensure_sorted_by(&mut other, &mut rule_is_less);
+        let array = merge_two_sorted_by(self.array, other, &mut rule_is_less);
+        unsafe { Sorted::uncheck_from_array(array, rule_is_less) }
+    }
+}
 
-struct StackIterator<T, const N: usize> {
-    stack: Stack<T, N>,
+/// Stably merge `slice[..mid]` and `slice[mid..]`, both sorted by `is_less`,
+/// without a buffer.
+///
+/// SymMerge (Kim and Kutzner, 2004): the longer half is split at its middle,
+/// the matching point of the other half is found by binary search,
+/// and rotating the two inner parts leaves two smaller merges.
+/// O(n log n) moves and O(log n) stack.
+///
+/// # Examples
+///
+/// ```
+/// use sort::merge_two_sorted::sym_merge_by;
+/// let mut array = [1, 4, 4, 7, 0, 4, 8];
+/// sym_merge_by(&mut array, 4, |a, b| a < b);
+/// assert_eq!(array, [0, 1, 4, 4, 4, 7, 8]);
+/// ```
+pub fn sym_merge_by<T, F>(slice: &mut [T], mid: usize, is_less: F)
+where
+    F: FnMut(&T, &T) -> bool,
+{
+    let mut is_less = is_less;
+    sym_merge(slice, mid, &mut is_less);
 }
 
-impl<T, const N: usize> Iterator for StackIterator<T, N> {
-    type Item = T;
+fn sym_merge<T, F>(slice: &mut [T], mid: usize, is_less: &mut F)
+where
+    F: FnMut(&T, &T) -> bool,
+{
+    let len = slice.len();
+    if mid == 0 || mid >= len {
+        return;
+    }
 
-    fn next(&mut self) -> Option<Self::Item> {
-        self.stack.pop()
+    if mid == 1 {
+        // insert the single left element after the right elements less than it
+        let index = mid + slice[mid..].partition_point(|x| is_less(x, &slice[0]));
+        slice[..index].rotate_left(1);
+        return;
+    }
+    if len - mid == 1 {
+        // insert the single right element after the left elements not greater
+        let index = slice[..mid].partition_point(|x| !is_less(&slice[mid], x));
+        slice[index..].rotate_right(1);
+        return;
     }
-}
 
-impl<T, const N: usize> IntoIterator for Stack<T, N> {
-    type Item = T;
-    type IntoIter = StackIterator<T, N>;
+    // find `start` so that `slice[start..mid]` and `slice[mid..end]`,
+    // symmetric around `half`, are the parts to swap
+    let half = len / 2;
+    let n = half + mid;
+    let (mut start, mut r) = if mid > half {
+        (n - len, half)
+    } else {
+        (0, mid)
+    };
+    let p = n - 1;
+    while start < r {
+        let c = (start + r) / 2;
+        if !is_less(&slice[p - c], &slice[c]) {
+            start = c + 1;
+        } else {
+            r = c;
+        }
+    }
+    let end = n - start;
 
-    fn into_iter(self) -> Self::IntoIter {
-        StackIterator { stack: self }
+    if start < mid && mid < end {
+        slice[start..end].rotate_left(mid - start);
     }
+    sym_merge(&mut slice[..half], start, is_less);
+    sym_merge(&mut slice[half..], end - half, is_less);
 }
 
-/// Merge two sorted arrays in place
+/// Stably merge `slice[..mid]` and `slice[mid..]`, both sorted by `is_less`,
+/// with a buffer of O(sqrt n) clones.
+///
+/// Rotations as in [`sym_merge_by`] split the merge into blocks,
+/// until the shorter half of a block fits the buffer and is merged linearly.
 ///
 /// # Examples
 ///
 /// ```
-/// #![feature(generic_const_exprs)]
-/// use sort::merge_two_sorted::merge_two_sorted_rs;
-/// let array1 = [1, 3, 5];
-/// let array2 = [2, 9];
-/// let array3 = merge_two_sorted_rs(array1, array2);
-/// assert_eq!(&array3, &[1, 2, 3, 5, 9]);
+/// use sort::merge_two_sorted::block_merge_by;
+/// let mut array = [1, 4, 4, 7, 0, 4, 8];
+/// block_merge_by(&mut array, 4, |a, b| a < b);
+/// assert_eq!(array, [0, 1, 4, 4, 4, 7, 8]);
 /// ```
-pub fn merge_two_sorted_rs<T, const N: usize, const M: usize>(a: [T; N], b: [T; M]) -> [T; N + M]
+pub fn block_merge_by<T, F>(slice: &mut [T], mid: usize, is_less: F)
 where
-    T: Ord + Copy,
+    T: Clone,
+    F: FnMut(&T, &T) -> bool,
 {
-    let mut stack = Stack::new();
-
-    let mut b = b.into_iter();
-    let mut i = 0;
-    while i < N {
-        match b.next() {
-            Some(x) => {
-                while i < N && a[i] <= x {
-                    stack.push(a[i]);
-                    i += 1;
-                }
-                stack.push(x);
+    let mut is_less = is_less;
+    let capacity = slice.len().isqrt().max(1);
+    let mut buffer = Vec::with_capacity(capacity);
+    block_merge(slice, mid, &mut buffer, capacity, &mut is_less);
+}
+
+fn block_merge<T, F>(
+    slice: &mut [T],
+    mid: usize,
+    buffer: &mut Vec<T>,
+    capacity: usize,
+    is_less: &mut F,
+) where
+    T: Clone,
+    F: FnMut(&T, &T) -> bool,
+{
+    let len = slice.len();
+    if mid == 0 || mid >= len || !is_less(&slice[mid], &slice[mid - 1]) {
+        return;
+    }
+
+    if mid <= capacity {
+        // move the left half aside and merge from the front
+        buffer.clear();
+        buffer.extend_from_slice(&slice[..mid]);
+        let (mut i, mut j) = (0, mid);
+        for k in 0..len {
+            if i == buffer.len() {
+                break;
+            }
+            if j < len && is_less(&slice[j], &buffer[i]) {
+                slice[k] = slice[j].clone();
+                j += 1;
+            } else {
+                slice[k] = buffer[i].clone();
+                i += 1;
             }
-            None => break,
         }
+        return;
+    }
+    if len - mid <= capacity {
+        // move the right half aside and merge from the back
+        buffer.clear();
+        buffer.extend_from_slice(&slice[mid..]);
+        let (mut i, mut j) = (mid, buffer.len());
+        for k in (0..len).rev() {
+            if j == 0 {
+                break;
+            }
+            if i > 0 && is_less(&buffer[j - 1], &slice[i - 1]) {
+                slice[k] = slice[i - 1].clone();
+                i -= 1;
+            } else {
+                slice[k] = buffer[j - 1].clone();
+                j -= 1;
+            }
+        }
+        return;
     }
 
-    a.into_iter().skip(i).for_each(|x| stack.push(x));
-    b.for_each(|x| stack.push(x));
+    // split the longer half at its middle and rotate, as SymMerge does
+    let (left_cut, right_cut) = if mid >= len - mid {
+        let left_cut = mid / 2;
+        let right_cut = mid + slice[mid..].partition_point(|x| is_less(x, &slice[left_cut]));
+        (left_cut, right_cut)
+    } else {
+        let right_cut = mid + (len - mid) / 2;
+        let left_cut = slice[..mid].partition_point(|x| !is_less(&slice[right_cut], x));
+        (left_cut, right_cut)
+    };
+    slice[left_cut..right_cut].rotate_left(mid - left_cut);
 
-    stack.take_if_full().unwrap()
+    let new_mid = left_cut + right_cut - mid;
+    let (low, high) = slice.split_at_mut(new_mid);
+    block_merge(low, left_cut, buffer, capacity, is_less);
+    block_merge(high, right_cut - new_mid, buffer, capacity, is_less);
 }
 
-pub fn merge_two_sorted(array: &mut [i32], split: usize) {
+pub fn merge_two_sorted(array: &mut [i32], split: usize) -> Result<(), CSortError> {
     use crate::csort::merge_two_sorted_array;
 
-    merge_two_sorted_array(array, split);
+    merge_two_sorted_array(array, split)
 }
 
 #[cfg(test)]
@@ -124,38 +486,234 @@ mod tests {
     #[test]
     fn one_element_array() {
         let mut array = [5, 2];
-        merge_two_sorted(&mut array, 1);
+        merge_two_sorted(&mut array, 1).unwrap();
         assert_eq!(array, [2, 5]);
     }
 
     #[test]
     fn empty_element_right() {
         let mut array = [0];
-        merge_two_sorted(&mut array, 0);
+        merge_two_sorted(&mut array, 0).unwrap();
         assert_eq!(array, [0]);
     }
 
     #[test]
     fn empty_element_left() {
         let mut array = [2];
-        merge_two_sorted(&mut array, 1);
+        merge_two_sorted(&mut array, 1).unwrap();
         assert_eq!(array, [2]);
     }
 
     #[test]
     fn empty_element_both() {
         let mut array: [i32; 0] = [];
-        merge_two_sorted(&mut array, 0);
+        merge_two_sorted(&mut array, 0).unwrap();
         assert_eq!(array, [0; 0]);
     }
 
     #[test]
     fn merge_array() {
         let mut array = [-1, 0, 3, 5, 6, -3, 5, 7, 8, 8, 9, 10];
-        merge_two_sorted(&mut array, 5);
+        merge_two_sorted(&mut array, 5).unwrap();
         assert_eq!(array, [-3, -1, 0, 3, 5, 5, 6, 7, 8, 8, 9, 10]);
     }
 
+    #[test]
+    fn merge_iter() {
+        let merged: Vec<_> = merge_iter_by([1, 4, 4, 7], [0, 4, 8], |a, b| a < b).collect();
+        assert_eq!(merged, [0, 1, 4, 4, 4, 7, 8]);
+        let merged: Vec<i32> = merge_iter_by([], [], |a, b| a < b).collect();
+        assert!(merged.is_empty());
+    }
+
+    #[test]
+    fn merge_by_stable() {
+        let a = [(1, 'a'), (2, 'a'), (2, 'b')];
+        let b = [(0, 'c'), (2, 'c'), (3, 'c')];
+        let merged = merge_two_sorted_by(a, b, |x, y| x.0 < y.0);
+        assert_eq!(
+            merged,
+            [(0, 'c'), (1, 'a'), (2, 'a'), (2, 'b'), (2, 'c'), (3, 'c')]
+        );
+    }
+
+    #[test]
+    fn merge_by_not_copy() {
+        let a = [String::from("a"), String::from("c")];
+        let b = [String::from("b")];
+        let merged = merge_two_sorted_by(a, b, |x, y| x < y);
+        assert_eq!(merged, ["a", "b", "c"]);
+    }
+
+    #[test]
+    fn merge_rs_not_copy() {
+        let a = [String::from("a"), String::from("c")];
+        let b = [String::from("b"), String::from("c")];
+        assert_eq!(merge_two_sorted_rs(a, b), ["a", "b", "c", "c"]);
+        assert_eq!(merge_two_sorted_rs([1, 1], [0, 1]), [0, 1, 1, 1]);
+    }
+
+    #[test]
+    fn merge_slices() {
+        let a: Vec<String> = ["apple", "fig", "pear"].map(String::from).to_vec();
+        let b: Vec<String> = ["banana", "fig", "kiwi"].map(String::from).to_vec();
+        assert_eq!(
+            merge_by(&a, &b, |x, y| x < y),
+            ["apple", "banana", "fig", "fig", "kiwi", "pear"]
+        );
+        assert_eq!(
+            merge_by_key(&["fig", "pear", "apple"], &["kiwi", "banana"], |s| s.len()),
+            ["fig", "pear", "kiwi", "apple", "banana"]
+        );
+
+        let mut out = vec![String::from("z")];
+        merge_into(&a, &[], &mut out);
+        assert_eq!(out, ["z", "apple", "fig", "pear"]);
+    }
+
+    #[test]
+    fn merge_slices_stable() {
+        let a = [(1, 'a'), (2, 'a'), (2, 'b')];
+        let b = [(0, 'c'), (2, 'c'), (3, 'c')];
+        assert_eq!(
+            merge_by_key(&a, &b, |x| x.0),
+            [(0, 'c'), (1, 'a'), (2, 'a'), (2, 'b'), (2, 'c'), (3, 'c')]
+        );
+    }
+
+    #[test]
+    fn merge_slices_panic() {
+        use std::panic::{catch_unwind, AssertUnwindSafe};
+
+        let a: Vec<String> = (0..10).map(|x| x.to_string()).collect();
+        let b = a.clone();
+        let mut out = Vec::new();
+        let mut calls = 0;
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            merge_into_by(&a, &b, &mut out, |x, y| {
+                calls += 1;
+                assert!(calls <= 5);
+                x < y
+            })
+        }));
+
+        assert!(result.is_err());
+        assert_eq!(out, ["0", "0", "1", "1", "2"]);
+        assert_eq!(a, b);
+    }
+
+    fn test_in_place_merge(merge: fn(&mut [(i32, usize)], usize)) {
+        use crate::test_utils::{bench_data, is_stable};
+
+        for (name, data) in bench_data() {
+            let records: Vec<_> = data.iter().map(|x| x.div_euclid(16)).zip(0..).collect();
+            for mid in [
+                0,
+                1,
+                2,
+                records.len() / 3,
+                records.len() / 2,
+                records.len() - 1,
+                records.len(),
+            ] {
+                let mut vec = records.clone();
+                let (left, right) = vec.split_at_mut(mid);
+                left.sort_by_key(|r| r.0);
+                right.sort_by_key(|r| r.0);
+                let mut expected = vec.clone();
+                expected.sort_by_key(|r| r.0);
+
+                merge(&mut vec, mid);
+                assert_eq!(vec, expected, "{} split at {}", name, mid);
+                assert!(is_stable(&vec, |r| r.0));
+            }
+        }
+    }
+
+    #[test]
+    fn sym_merge() {
+        test_in_place_merge(|slice, mid| sym_merge_by(slice, mid, |a, b| a.0 < b.0));
+
+        let mut array: [String; 0] = [];
+        sym_merge_by(&mut array, 0, |a, b| a < b);
+        let mut vec: Vec<String> = ["b", "d", "a", "c", "e"].map(String::from).to_vec();
+        sym_merge_by(&mut vec, 2, |a, b| a < b);
+        assert_eq!(vec, ["a", "b", "c", "d", "e"]);
+    }
+
+    #[test]
+    fn block_merge() {
+        test_in_place_merge(|slice, mid| block_merge_by(slice, mid, |a, b| a.0 < b.0));
+
+        let mut vec: Vec<String> = ["b", "d", "a", "c", "e"].map(String::from).to_vec();
+        block_merge_by(&mut vec, 2, |a, b| a < b);
+        assert_eq!(vec, ["a", "b", "c", "d", "e"]);
+    }
+
+    #[test]
+    fn merge_join() {
+        use Merged::*;
+        let merged: Vec<_> = merge_join_by([1, 2, 2, 5], [2, 5, 5, 6], |a, b| a < b).collect();
+        assert_eq!(
+            merged,
+            [Left(1), Both(2, 2), Left(2), Both(5, 5), Right(5), Right(6)]
+        );
+        let merged: Vec<Merged<i32>> = merge_join_by([], [], |a, b| a < b).collect();
+        assert!(merged.is_empty());
+    }
+
+    mod sorted {
+        use crate::{read_bench_data, Solution, Stable};
+
+        #[test]
+        fn empty() {
+            let a = Stable::sort([] as [i32; 0]);
+            let b = Stable::sort([2, 1]);
+            assert_eq!(*a.merge(b), [1, 2]);
+        }
+
+        #[test]
+        fn stable() {
+            let a = Stable::sort_by_key([(2, 'a'), (1, 'a'), (2, 'b')], |x| x.0);
+            let b = Stable::sort_by_key([(2, 'c'), (0, 'c')], |x| x.0);
+            let merged = a.merge(b);
+            assert_eq!(*merged, [(0, 'c'), (1, 'a'), (2, 'a'), (2, 'b'), (2, 'c')]);
+        }
+
+        #[test]
+        fn keep_rule() {
+            let a = Stable::sort_by([1, 5, 3], |a, b| b.cmp(a));
+            let b = Stable::sort_by([4, 2], |a, b| b.cmp(a));
+            let merged = a.merge(b);
+            assert_eq!(*merged, [5, 4, 3, 2, 1]);
+            assert_eq!(merged.lower_bound(&3), 2);
+        }
+
+        #[test]
+        fn other_rule() {
+            let a = Stable::sort_method([1, 5, 3], i32::lt);
+            let b = Stable::sort_method([4, 2, 6], i32::gt);
+            let merged = a.merge(b);
+            assert_eq!(*merged, [1, 2, 3, 4, 5, 6]);
+            assert!(merged.force_check());
+
+            let a = Stable::sort_by_key([(2, 'a'), (1, 'a')], |x| x.0);
+            let b = Stable::sort_by_key([(1, 'b'), (2, 'c'), (1, 'c')], |x| x.1);
+            let merged = a.merge(b);
+            assert_eq!(*merged, [(1, 'a'), (1, 'b'), (1, 'c'), (2, 'a'), (2, 'c')]);
+        }
+
+        #[test]
+        fn bench_data() {
+            let a: [i32; 1000] = read_bench_data!("gaussian-with-noise");
+            let b: [i32; 1000] = read_bench_data!("trend-increasing");
+            let merged = Stable::sort(a).merge(Stable::sort(b));
+            let mut expected = [a, b].concat();
+            expected.sort();
+            assert_eq!(merged.as_slice(), expected);
+        }
+    }
+
     mod bench {
         extern crate test;
         use super::*;
@@ -170,5 +728,29 @@ mod tests {
 
             b.iter(|| merge_two_sorted(&mut array, 500))
         }
+
+        fn bench_array1k(b: &mut test::Bencher, merge: fn(&mut [i32], usize)) {
+            let mut array: [i32; 1000] = gen_random(-1000..1000);
+            let (array1, array2) = array.split_at_mut(500);
+            array1.sort();
+            array2.sort();
+
+            b.iter(|| merge(&mut array.clone(), 500))
+        }
+
+        #[bench]
+        fn array1k_buffered(b: &mut test::Bencher) {
+            bench_array1k(b, |array, split| merge_two_sorted(array, split).unwrap());
+        }
+
+        #[bench]
+        fn array1k_sym_merge(b: &mut test::Bencher) {
+            bench_array1k(b, |slice, mid| sym_merge_by(slice, mid, i32::lt));
+        }
+
+        #[bench]
+        fn array1k_block_merge(b: &mut test::Bencher) {
+            bench_array1k(b, |slice, mid| block_merge_by(slice, mid, i32::lt));
+        }
     }
 }
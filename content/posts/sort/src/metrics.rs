@@ -0,0 +1,229 @@
+//! Measures of disorder, how far a slice is from being sorted.
+//!
+//! All of them are 0 (or 1 for `runs`) for a sorted slice,
+//! see Estivill-Castro and Wood, "A survey of adaptive sorting algorithms".
+//!
+//! # Examples
+//!
+//! ```
+//! use sort::metrics::Disorder;
+//! let disorder = Disorder::of(&[2, 1, 3, 5, 4]);
+//! assert_eq!(disorder.inversions, 2);
+//! assert_eq!(disorder.runs, 3);
+//! assert_eq!(disorder.rem, 2);
+//! assert_eq!(disorder.max, 1);
+//! ```
+
+use serde::Serialize;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Disorder {
+    pub len: usize,
+    /// Pairs in the wrong order, see [`inversions`].
+    pub inversions: usize,
+    /// Ascending runs, see [`runs`].
+    pub runs: usize,
+    /// Elements to remove to leave a sorted sequence, see [`rem`].
+    pub rem: usize,
+    /// See [`osc`].
+    pub osc: usize,
+    /// Greatest distance of an element from its sorted position, see [`max`].
+    pub max: usize,
+    /// Distinct values, see [`distinct`].
+    pub distinct: usize,
+}
+
+impl Disorder {
+    pub fn of<T: Ord>(slice: &[T]) -> Self {
+        Disorder {
+            len: slice.len(),
+            inversions: inversions(slice),
+            runs: runs(slice),
+            rem: rem(slice),
+            osc: osc(slice),
+            max: max(slice),
+            distinct: distinct(slice),
+        }
+    }
+}
+
+/// Number of pairs `i < j` with `slice[j] < slice[i]`.
+///
+/// Counted while merge sorting references, in O(n log n).
+pub fn inversions<T: Ord>(slice: &[T]) -> usize {
+    fn sort_count<'a, T: Ord>(refs: &mut [&'a T], buffer: &mut Vec<&'a T>) -> usize {
+        if refs.len() <= 1 {
+            return 0;
+        }
+
+        let half = refs.len() / 2;
+        let (left, right) = refs.split_at_mut(half);
+        let mut count = sort_count(left, buffer) + sort_count(right, buffer);
+
+        buffer.clear();
+        let (mut i, mut j) = (0, 0);
+        while i < left.len() && j < right.len() {
+            if right[j] < left[i] {
+                // `right[j]` is less than every remaining element of `left`
+                count += left.len() - i;
+                buffer.push(right[j]);
+                j += 1;
+            } else {
+                buffer.push(left[i]);
+                i += 1;
+            }
+        }
+        buffer.extend_from_slice(&left[i..]);
+        buffer.extend_from_slice(&right[j..]);
+        refs.copy_from_slice(buffer);
+        count
+    }
+
+    let mut refs: Vec<&T> = slice.iter().collect();
+    sort_count(&mut refs, &mut Vec::with_capacity(slice.len()))
+}
+
+/// Number of maximal ascending (non-decreasing) runs, 0 for an empty slice.
+pub fn runs<T: Ord>(slice: &[T]) -> usize {
+    if slice.is_empty() {
+        return 0;
+    }
+    1 + slice.windows(2).filter(|w| w[1] < w[0]).count()
+}
+
+/// Length of the longest non-decreasing subsequence, in O(n log n).
+pub fn longest_sorted_subsequence<T: Ord>(slice: &[T]) -> usize {
+    // least last element of the sorted subsequences of every length
+    let mut tails: Vec<&T> = Vec::new();
+    for x in slice {
+        let index = tails.partition_point(|tail| *tail <= x);
+        if index == tails.len() {
+            tails.push(x);
+        } else {
+            tails[index] = x;
+        }
+    }
+    tails.len()
+}
+
+/// Number of elements to remove to leave a sorted sequence.
+pub fn rem<T: Ord>(slice: &[T]) -> usize {
+    slice.len() - longest_sorted_subsequence(slice)
+}
+
+/// Sum over all elements of how many steps between neighbours cross them,
+/// a step `slice[j], slice[j + 1]` crosses `x` if `x` lies strictly between.
+///
+/// Counted by binary search in O(n log n): the steps crossing `x` are the
+/// steps with their lower end below `x`, minus those entirely below `x`.
+pub fn osc<T: Ord>(slice: &[T]) -> usize {
+    let (mut lows, mut highs): (Vec<&T>, Vec<&T>) = slice
+        .windows(2)
+        .filter(|w| w[0] != w[1])
+        .map(|w| (&w[0], &w[1]))
+        .map(|(a, b)| (a.min(b), a.max(b)))
+        .unzip();
+    lows.sort();
+    highs.sort();
+
+    slice
+        .iter()
+        .map(|x| lows.partition_point(|low| *low < x) - highs.partition_point(|high| *high <= x))
+        .sum()
+}
+
+/// Greatest distance between the index of an element and its index
+/// after a stable sort.
+pub fn max<T: Ord>(slice: &[T]) -> usize {
+    let mut indices: Vec<usize> = (0..slice.len()).collect();
+    indices.sort_by_key(|&i| &slice[i]);
+    indices
+        .iter()
+        .enumerate()
+        .map(|(sorted, &original)| sorted.abs_diff(original))
+        .max()
+        .unwrap_or(0)
+}
+
+pub fn distinct<T: Ord>(slice: &[T]) -> usize {
+    let mut refs: Vec<&T> = slice.iter().collect();
+    refs.sort();
+    refs.dedup();
+    refs.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::bench_data;
+
+    fn brute_inversions(slice: &[i32]) -> usize {
+        (0..slice.len())
+            .map(|i| slice[i + 1..].iter().filter(|x| **x < slice[i]).count())
+            .sum()
+    }
+
+    fn brute_osc(slice: &[i32]) -> usize {
+        slice
+            .iter()
+            .map(|x| {
+                slice
+                    .windows(2)
+                    .filter(|w| w[0].min(w[1]) < *x && *x < w[0].max(w[1]))
+                    .count()
+            })
+            .sum()
+    }
+
+    #[test]
+    fn sorted() {
+        let disorder = Disorder::of(&[1, 2, 2, 3]);
+        assert_eq!(
+            disorder,
+            Disorder {
+                len: 4,
+                inversions: 0,
+                runs: 1,
+                rem: 0,
+                osc: 0,
+                max: 0,
+                distinct: 3,
+            }
+        );
+        assert_eq!(Disorder::of::<i32>(&[]), Disorder::default());
+    }
+
+    #[test]
+    fn reversed() {
+        let disorder = Disorder::of(&[5, 4, 3, 2, 1]);
+        assert_eq!(disorder.inversions, 10);
+        assert_eq!(disorder.runs, 5);
+        assert_eq!(disorder.rem, 4);
+        assert_eq!(disorder.osc, 0);
+        assert_eq!(disorder.max, 4);
+        assert_eq!(disorder.distinct, 5);
+    }
+
+    #[test]
+    fn oscillating() {
+        let slice = [1, 5, 2, 4, 3];
+        assert_eq!(osc(&slice), brute_osc(&slice));
+        assert_eq!(osc(&slice), 6);
+        assert_eq!(longest_sorted_subsequence(&slice), 3);
+        assert_eq!(longest_sorted_subsequence(&[3, 3, 1, 3]), 3);
+    }
+
+    #[test]
+    fn bench_data_against_brute_force() {
+        for (name, data) in bench_data() {
+            let data = &data[..1000];
+            assert_eq!(inversions(data), brute_inversions(data), "{}", name);
+            assert_eq!(osc(data), brute_osc(data), "{}", name);
+
+            let mut sorted = data.to_vec();
+            sorted.sort();
+            assert_eq!(Disorder::of(&sorted).inversions, 0);
+            assert_eq!(Disorder::of(&sorted).distinct, Disorder::of(data).distinct);
+        }
+    }
+}
@@ -0,0 +1,431 @@
+//! Stable merge sort in pure Rust, splitting and merging in parallel with rayon.
+//!
+//! Both halves are sorted with `rayon::join`, then merged in parallel:
+//! the median of the longer run is located in the other run by binary search,
+//! which splits the merge into two independent merges.
+//!
+//! Elements only need to be `Send`, every thread works on its own part.
+//! The comparator is shared between threads, so it must be `Fn + Sync`.
+//! [`Solution::sort_method`] takes an `FnMut` and therefore sorts sequentially,
+//! `sort` / `sort_slice` (comparing with `Ord`) and
+//! [`ParallelMerge::par_sort_method`] / [`ParallelMerge::par_sort_slice_method`]
+//! sort in parallel.
+//!
+//! # Examples
+//!
+//! ```
+//! use sort::{ParallelMerge, SliceSolution};
+//! let mut vec: Vec<i32> = (0..100_000).rev().collect();
+//! let sorted = ParallelMerge::sort_slice(&mut vec);
+//! assert!(sorted.force_check());
+//! ```
+
+use crate::{SliceSolution, Solution, Sorted, SortedSlice, StableSolution};
+use std::mem::MaybeUninit;
+
+/// Runs not longer than this are sorted by insertion.
+const INSERTION: usize = 20;
+
+/// Sorts and merges shorter than this are not split between threads.
+const SEQUENTIAL: usize = 2048;
+
+/// Parallel only through `Ord` and the `par_*` methods, see the module doc.
+#[derive(Copy, Clone)]
+pub struct ParallelMerge;
+
+impl StableSolution for ParallelMerge {}
+
+impl<T: Send, const N: usize> Solution<T, N> for ParallelMerge {
+    fn sort_method<F>(array: [T; N], is_less: F) -> Sorted<T, N, impl FnMut(&T, &T) -> bool>
+    where
+        F: FnMut(&T, &T) -> bool,
+    {
+        let mut is_less = is_less;
+        let mut array = array;
+        merge_sort(&mut array, &mut buffer(N), &mut is_less);
+        unsafe { Sorted::uncheck_from_array(array, is_less) }
+    }
+
+    fn sort(array: [T; N]) -> Sorted<T, N, impl FnMut(&T, &T) -> bool>
+    where
+        T: Ord,
+    {
+        Self::par_sort_method(array, T::lt)
+    }
+}
+
+impl<T: Send> SliceSolution<T> for ParallelMerge {
+    fn sort_slice_method<F>(
+        slice: &mut [T],
+        is_less: F,
+    ) -> SortedSlice<'_, T, impl FnMut(&T, &T) -> bool>
+    where
+        F: FnMut(&T, &T) -> bool,
+    {
+        let mut is_less = is_less;
+        merge_sort(slice, &mut buffer(slice.len()), &mut is_less);
+        unsafe { SortedSlice::uncheck_from_slice(slice, is_less) }
+    }
+
+    fn sort_slice(slice: &mut [T]) -> SortedSlice<'_, T, impl FnMut(&T, &T) -> bool>
+    where
+        T: Ord,
+    {
+        Self::par_sort_slice_method(slice, T::lt)
+    }
+}
+
+impl ParallelMerge {
+    /// Sort in parallel with a comparator shared between threads.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sort::ParallelMerge;
+    /// let sorted = ParallelMerge::par_sort_method([3, 1, 2], |a, b| a > b);
+    /// assert_eq!(*sorted, [3, 2, 1]);
+    /// ```
+    pub fn par_sort_method<T, F, const N: usize>(array: [T; N], is_less: F) -> Sorted<T, N, F>
+    where
+        T: Send,
+        F: Fn(&T, &T) -> bool + Sync,
+    {
+        let mut array = array;
+        par_merge_sort(&mut array, &mut buffer(N), &is_less);
+        unsafe { Sorted::uncheck_from_array(array, is_less) }
+    }
+
+    /// See [`ParallelMerge::par_sort_method`].
+    pub fn par_sort_slice_method<T, F>(slice: &mut [T], is_less: F) -> SortedSlice<'_, T, F>
+    where
+        T: Send,
+        F: Fn(&T, &T) -> bool + Sync,
+    {
+        par_merge_sort(slice, &mut buffer(slice.len()), &is_less);
+        unsafe { SortedSlice::uncheck_from_slice(slice, is_less) }
+    }
+}
+
+fn buffer<T>(len: usize) -> Vec<MaybeUninit<T>> {
+    let mut buffer = Vec::with_capacity(len);
+    buffer.resize_with(len, MaybeUninit::uninit);
+    buffer
+}
+
+fn insertion_sort<T, F>(slice: &mut [T], is_less: &mut F)
+where
+    F: FnMut(&T, &T) -> bool,
+{
+    for i in 1..slice.len() {
+        let mut j = i;
+        while j > 0 && is_less(&slice[j], &slice[j - 1]) {
+            slice.swap(j - 1, j);
+            j -= 1;
+        }
+    }
+}
+
+fn merge_sort<T, F>(slice: &mut [T], buffer: &mut [MaybeUninit<T>], is_less: &mut F)
+where
+    F: FnMut(&T, &T) -> bool,
+{
+    if slice.len() <= INSERTION {
+        insertion_sort(slice, is_less);
+        return;
+    }
+
+    let half = slice.len() / 2;
+    let (left, right) = slice.split_at_mut(half);
+    let (left_buffer, right_buffer) = buffer.split_at_mut(half);
+    merge_sort(left, left_buffer, is_less);
+    merge_sort(right, right_buffer, is_less);
+
+    merge(left, right, buffer, is_less);
+    unsafe { copy_back(buffer, slice) };
+}
+
+fn par_merge_sort<T, F>(slice: &mut [T], buffer: &mut [MaybeUninit<T>], is_less: &F)
+where
+    T: Send,
+    F: Fn(&T, &T) -> bool + Sync,
+{
+    if slice.len() < SEQUENTIAL {
+        merge_sort(slice, buffer, &mut |a, b| is_less(a, b));
+        return;
+    }
+
+    let half = slice.len() / 2;
+    let (left, right) = slice.split_at_mut(half);
+    let (left_buffer, right_buffer) = buffer.split_at_mut(half);
+    rayon::join(
+        || par_merge_sort(left, left_buffer, is_less),
+        || par_merge_sort(right, right_buffer, is_less),
+    );
+
+    par_merge(left, right, buffer, is_less);
+    unsafe { copy_back(buffer, slice) };
+}
+
+/// Move the merged elements from `buffer` back to `slice`.
+///
+/// # Safety
+///
+/// `buffer` must hold a bitwise copy of every element of `slice`.
+unsafe fn copy_back<T>(buffer: &[MaybeUninit<T>], slice: &mut [T]) {
+    debug_assert_eq!(buffer.len(), slice.len());
+    std::ptr::copy_nonoverlapping(buffer.as_ptr() as *const T, slice.as_mut_ptr(), slice.len());
+}
+
+/// Merge bitwise copies of `left` and `right` into `dest`, left first on ties.
+///
+/// `left` and `right` are only read, so they still own every element
+/// if `is_less` panics.
+fn merge<T, F>(left: &[T], right: &[T], dest: &mut [MaybeUninit<T>], is_less: &mut F)
+where
+    F: FnMut(&T, &T) -> bool,
+{
+    debug_assert_eq!(left.len() + right.len(), dest.len());
+    let (mut i, mut j) = (0, 0);
+    for slot in dest.iter_mut() {
+        let take_right = j < right.len() && (i >= left.len() || is_less(&right[j], &left[i]));
+        let src = if take_right {
+            j += 1;
+            &right[j - 1]
+        } else {
+            i += 1;
+            &left[i - 1]
+        };
+        slot.write(unsafe { std::ptr::read(src) });
+    }
+}
+
+fn par_merge<T, F>(left: &mut [T], right: &mut [T], dest: &mut [MaybeUninit<T>], is_less: &F)
+where
+    T: Send,
+    F: Fn(&T, &T) -> bool + Sync,
+{
+    if left.is_empty() || right.is_empty() || dest.len() < SEQUENTIAL {
+        merge(left, right, dest, &mut |a, b| is_less(a, b));
+        return;
+    }
+
+    // elements of `right` equal to a pivot from `left` go after it, and
+    // elements of `left` equal to a pivot from `right` go before it
+    let (left_mid, right_mid) = if left.len() >= right.len() {
+        let left_mid = left.len() / 2;
+        let pivot = &left[left_mid];
+        (left_mid, right.partition_point(|x| is_less(x, pivot)))
+    } else {
+        let right_mid = right.len() / 2;
+        let pivot = &right[right_mid];
+        (left.partition_point(|x| !is_less(pivot, x)), right_mid)
+    };
+
+    let (left_low, left_high) = left.split_at_mut(left_mid);
+    let (right_low, right_high) = right.split_at_mut(right_mid);
+    let (dest_low, dest_high) = dest.split_at_mut(left_mid + right_mid);
+    rayon::join(
+        || par_merge(left_low, right_low, dest_low, is_less),
+        || par_merge(left_high, right_high, dest_high, is_less),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::*;
+
+    #[test]
+    fn simple() {
+        let array = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9];
+        let a = ParallelMerge::sort_method(array, |a, b| a < b);
+        assert!(a.force_check());
+        assert_eq!(a.take(), [0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+    }
+
+    #[test]
+    fn empty() {
+        test_empty(ParallelMerge);
+    }
+
+    #[test]
+    fn random() {
+        test_random(ParallelMerge);
+    }
+
+    #[test]
+    fn stroll() {
+        test_stroll(ParallelMerge);
+    }
+
+    #[test]
+    fn trend_increasing() {
+        test_trend_increasing(ParallelMerge);
+    }
+
+    #[test]
+    fn gaussian_with_noise() {
+        test_gaussian_with_noise(ParallelMerge);
+    }
+
+    #[test]
+    fn low_sample_sin_with_noise() {
+        test_low_sample_sin_with_noise(ParallelMerge);
+    }
+
+    #[test]
+    fn high_sample_sin_with_noise() {
+        test_high_sample_sin_with_noise(ParallelMerge);
+    }
+
+    #[test]
+    fn not_copy() {
+        let mut vec: Vec<String> = (0..10_000).rev().map(|x| x.to_string()).collect();
+        let mut expected = vec.clone();
+        expected.sort();
+        let sorted = ParallelMerge::sort_slice(&mut vec);
+        assert_eq!(*sorted, expected);
+    }
+
+    #[test]
+    fn panic_in_comparator() {
+        use std::panic::{catch_unwind, AssertUnwindSafe};
+        use std::sync::atomic::{AtomicUsize, Ordering::Relaxed};
+
+        let mut vec: Vec<String> = (0..10_000).rev().map(|x| x.to_string()).collect();
+        let calls = AtomicUsize::new(0);
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            ParallelMerge::par_sort_slice_method(&mut vec, |a, b| {
+                assert!(calls.fetch_add(1, Relaxed) < 50_000);
+                a < b
+            });
+        }));
+        assert!(result.is_err());
+
+        // every element is still there exactly once
+        vec.sort();
+        let mut expected: Vec<String> = (0..10_000).map(|x| x.to_string()).collect();
+        expected.sort();
+        assert_eq!(vec, expected);
+    }
+
+    mod stability {
+        use super::*;
+
+        #[test]
+        fn random() {
+            test_stable_random(ParallelMerge);
+        }
+
+        #[test]
+        fn stroll() {
+            test_stable_stroll(ParallelMerge);
+        }
+
+        #[test]
+        fn trend_increasing() {
+            test_stable_trend_increasing(ParallelMerge);
+        }
+
+        #[test]
+        fn gaussian_with_noise() {
+            test_stable_gaussian_with_noise(ParallelMerge);
+        }
+
+        #[test]
+        fn low_sample_sin_with_noise() {
+            test_stable_low_sample_sin_with_noise(ParallelMerge);
+        }
+
+        #[test]
+        fn high_sample_sin_with_noise() {
+            test_stable_high_sample_sin_with_noise(ParallelMerge);
+        }
+
+        /// `sort_by_key` is sequential, so check the parallel path separately.
+        #[test]
+        fn parallel() {
+            for (_, data) in bench_data() {
+                let data: Vec<_> = data.into_iter().cycle().take(50_000).collect();
+                let mut records: Vec<_> = data.into_iter().zip(0..).collect();
+                let key = |r: &(i32, usize)| r.0.div_euclid(16);
+                let sorted =
+                    ParallelMerge::par_sort_slice_method(&mut records, |a, b| key(a) < key(b));
+                assert!(is_stable(&sorted, key));
+            }
+        }
+    }
+
+    mod slice {
+        use super::*;
+
+        #[test]
+        fn empty() {
+            test_slice_empty(ParallelMerge);
+        }
+
+        #[test]
+        fn random() {
+            test_slice_random(ParallelMerge);
+        }
+
+        #[test]
+        fn stroll() {
+            test_slice_stroll(ParallelMerge);
+        }
+
+        #[test]
+        fn trend_increasing() {
+            test_slice_trend_increasing(ParallelMerge);
+        }
+
+        #[test]
+        fn gaussian_with_noise() {
+            test_slice_gaussian_with_noise(ParallelMerge);
+        }
+
+        #[test]
+        fn low_sample_sin_with_noise() {
+            test_slice_low_sample_sin_with_noise(ParallelMerge);
+        }
+
+        #[test]
+        fn high_sample_sin_with_noise() {
+            test_slice_high_sample_sin_with_noise(ParallelMerge);
+        }
+
+        mod stability {
+            use super::*;
+
+            #[test]
+            fn random() {
+                test_slice_stable_random(ParallelMerge);
+            }
+
+            #[test]
+            fn stroll() {
+                test_slice_stable_stroll(ParallelMerge);
+            }
+
+            #[test]
+            fn trend_increasing() {
+                test_slice_stable_trend_increasing(ParallelMerge);
+            }
+
+            #[test]
+            fn gaussian_with_noise() {
+                test_slice_stable_gaussian_with_noise(ParallelMerge);
+            }
+
+            #[test]
+            fn low_sample_sin_with_noise() {
+                test_slice_stable_low_sample_sin_with_noise(ParallelMerge);
+            }
+
+            #[test]
+            fn high_sample_sin_with_noise() {
+                test_slice_stable_high_sample_sin_with_noise(ParallelMerge);
+            }
+        }
+    }
+}
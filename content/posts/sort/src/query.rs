@@ -0,0 +1,294 @@
+//! Binary search queries answered with the `rule_is_less` stored in
+//! [`Sorted`], [`SortedSlice`] and [`SortedVec`].
+
+use crate::{Sorted, SortedSlice, SortedVec};
+use std::ops::{Bound, Range, RangeBounds};
+
+impl<'a, T, F: FnMut(&T, &T) -> bool> SortedSlice<'a, T, F> {
+    /// Index of the first element that is not less than `value`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sort::{SliceSolution, Stable};
+    /// let mut vec = vec![1, 2, 2, 2, 5];
+    /// let sorted = Stable::sort_slice(&mut vec);
+    /// assert_eq!(sorted.lower_bound(&2), 1);
+    /// assert_eq!(sorted.lower_bound(&3), 4);
+    /// ```
+    pub fn lower_bound(&self, value: &T) -> usize {
+        let mut is_less = self.rule_is_less.borrow_mut();
+        self.slice.partition_point(|x| is_less(x, value))
+    }
+
+    /// Index of the first element that is greater than `value`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sort::{SliceSolution, Stable};
+    /// let mut vec = vec![1, 2, 2, 2, 5];
+    /// let sorted = Stable::sort_slice(&mut vec);
+    /// assert_eq!(sorted.upper_bound(&2), 4);
+    /// assert_eq!(sorted.upper_bound(&0), 0);
+    /// ```
+    pub fn upper_bound(&self, value: &T) -> usize {
+        let mut is_less = self.rule_is_less.borrow_mut();
+        self.slice.partition_point(|x| !is_less(value, x))
+    }
+
+    /// Indices of all elements equal to `value`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sort::{SliceSolution, Stable};
+    /// let mut vec = vec![1, 2, 2, 2, 5];
+    /// let sorted = Stable::sort_slice(&mut vec);
+    /// assert_eq!(sorted.equal_range(&2), 1..4);
+    /// assert_eq!(sorted.equal_range(&3), 4..4);
+    /// ```
+    pub fn equal_range(&self, value: &T) -> Range<usize> {
+        self.lower_bound(value)..self.upper_bound(value)
+    }
+
+    pub fn contains(&self, value: &T) -> bool {
+        let index = self.lower_bound(value);
+        index < self.slice.len() && !(self.rule_is_less.borrow_mut())(value, &self.slice[index])
+    }
+
+    /// Number of elements less than `value`.
+    pub fn rank(&self, value: &T) -> usize {
+        self.lower_bound(value)
+    }
+
+    /// Indices of the elements whose values fall into `range`.
+    pub fn index_range<R: RangeBounds<T>>(&self, range: R) -> Range<usize> {
+        let start = match range.start_bound() {
+            Bound::Included(value) => self.lower_bound(value),
+            Bound::Excluded(value) => self.upper_bound(value),
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(value) => self.upper_bound(value),
+            Bound::Excluded(value) => self.lower_bound(value),
+            Bound::Unbounded => self.slice.len(),
+        };
+        start..end.max(start)
+    }
+
+    /// Sorted view of the elements whose values fall into `range`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sort::{SliceSolution, Stable};
+    /// let mut vec = vec![5, 1, 4, 2, 3];
+    /// let sorted = Stable::sort_slice(&mut vec);
+    /// assert_eq!(*sorted.range(2..4), [2, 3]);
+    /// assert_eq!(*sorted.range(2..=4), [2, 3, 4]);
+    /// assert_eq!(*sorted.range(4..), [4, 5]);
+    /// ```
+    pub fn range<R: RangeBounds<T>>(
+        &self,
+        range: R,
+    ) -> SortedSlice<'_, T, impl FnMut(&T, &T) -> bool + '_> {
+        let range = self.index_range(range);
+        let rule_is_less = &self.rule_is_less;
+        unsafe {
+            SortedSlice::uncheck_from_slice(&self.slice[range], move |a: &T, b: &T| {
+                (rule_is_less.borrow_mut())(a, b)
+            })
+        }
+    }
+}
+
+impl<T, const N: usize, F: FnMut(&T, &T) -> bool> Sorted<T, N, F> {
+    /// See [`SortedSlice::lower_bound`].
+    pub fn lower_bound(&self, value: &T) -> usize {
+        self.as_sorted_slice().lower_bound(value)
+    }
+
+    /// See [`SortedSlice::upper_bound`].
+    pub fn upper_bound(&self, value: &T) -> usize {
+        self.as_sorted_slice().upper_bound(value)
+    }
+
+    /// See [`SortedSlice::equal_range`].
+    pub fn equal_range(&self, value: &T) -> Range<usize> {
+        self.as_sorted_slice().equal_range(value)
+    }
+
+    /// See [`SortedSlice::contains`].
+    pub fn contains(&self, value: &T) -> bool {
+        self.as_sorted_slice().contains(value)
+    }
+
+    /// See [`SortedSlice::rank`].
+    pub fn rank(&self, value: &T) -> usize {
+        self.as_sorted_slice().rank(value)
+    }
+
+    /// See [`SortedSlice::index_range`].
+    pub fn index_range<R: RangeBounds<T>>(&self, range: R) -> Range<usize> {
+        self.as_sorted_slice().index_range(range)
+    }
+
+    /// See [`SortedSlice::range`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sort::{Solution, Stable};
+    /// let sorted = Stable::sort([5, 1, 4, 2, 3]);
+    /// assert_eq!(*sorted.range(2..4), [2, 3]);
+    /// ```
+    pub fn range<R: RangeBounds<T>>(
+        &self,
+        range: R,
+    ) -> SortedSlice<'_, T, impl FnMut(&T, &T) -> bool + '_> {
+        let range = self.index_range(range);
+        let rule_is_less = &self.rule_is_less;
+        unsafe {
+            SortedSlice::uncheck_from_slice(&self.array[range], move |a: &T, b: &T| {
+                (rule_is_less.borrow_mut())(a, b)
+            })
+        }
+    }
+}
+
+impl<T, F: FnMut(&T, &T) -> bool> SortedVec<T, F> {
+    /// See [`SortedSlice::lower_bound`].
+    pub fn lower_bound(&self, value: &T) -> usize {
+        self.as_sorted_slice().lower_bound(value)
+    }
+
+    /// See [`SortedSlice::upper_bound`].
+    pub fn upper_bound(&self, value: &T) -> usize {
+        self.as_sorted_slice().upper_bound(value)
+    }
+
+    /// See [`SortedSlice::equal_range`].
+    pub fn equal_range(&self, value: &T) -> Range<usize> {
+        self.as_sorted_slice().equal_range(value)
+    }
+
+    /// See [`SortedSlice::contains`].
+    pub fn contains(&self, value: &T) -> bool {
+        self.as_sorted_slice().contains(value)
+    }
+
+    /// See [`SortedSlice::rank`].
+    pub fn rank(&self, value: &T) -> usize {
+        self.as_sorted_slice().rank(value)
+    }
+
+    /// See [`SortedSlice::index_range`].
+    pub fn index_range<R: RangeBounds<T>>(&self, range: R) -> Range<usize> {
+        self.as_sorted_slice().index_range(range)
+    }
+
+    /// See [`SortedSlice::range`].
+    pub fn range<R: RangeBounds<T>>(
+        &self,
+        range: R,
+    ) -> SortedSlice<'_, T, impl FnMut(&T, &T) -> bool + '_> {
+        let range = self.index_range(range);
+        let rule_is_less = &self.rule_is_less;
+        unsafe {
+            SortedSlice::uncheck_from_slice(&self.vec[range], move |a: &T, b: &T| {
+                (rule_is_less.borrow_mut())(a, b)
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{read_bench_data, SliceSolution, Solution, Stable};
+    use std::ops::Bound::{Excluded, Included};
+
+    #[test]
+    fn empty() {
+        let sorted = Stable::sort([] as [i32; 0]);
+        assert_eq!(sorted.lower_bound(&0), 0);
+        assert_eq!(sorted.upper_bound(&0), 0);
+        assert_eq!(sorted.equal_range(&0), 0..0);
+        assert!(!sorted.contains(&0));
+        assert!(sorted.range(..).is_empty());
+    }
+
+    #[test]
+    fn bounds() {
+        let sorted = Stable::sort([1, 3, 3, 5]);
+        assert_eq!(sorted.lower_bound(&0), 0);
+        assert_eq!(sorted.lower_bound(&3), 1);
+        assert_eq!(sorted.lower_bound(&4), 3);
+        assert_eq!(sorted.lower_bound(&6), 4);
+        assert_eq!(sorted.upper_bound(&0), 0);
+        assert_eq!(sorted.upper_bound(&3), 3);
+        assert_eq!(sorted.upper_bound(&5), 4);
+    }
+
+    #[test]
+    fn contains() {
+        let sorted = Stable::sort([1, 3, 3, 5]);
+        assert!(sorted.contains(&1));
+        assert!(sorted.contains(&3));
+        assert!(sorted.contains(&5));
+        assert!(!sorted.contains(&0));
+        assert!(!sorted.contains(&4));
+        assert!(!sorted.contains(&6));
+    }
+
+    #[test]
+    fn rank() {
+        let sorted = Stable::sort([1, 3, 3, 5]);
+        assert_eq!(sorted.rank(&1), 0);
+        assert_eq!(sorted.rank(&4), 3);
+        assert_eq!(sorted.rank(&9), 4);
+    }
+
+    #[test]
+    fn range() {
+        let sorted = Stable::sort([1, 3, 3, 5, 7]);
+        assert_eq!(*sorted.range(..), [1, 3, 3, 5, 7]);
+        assert_eq!(*sorted.range(3..), [3, 3, 5, 7]);
+        assert_eq!(*sorted.range(..5), [1, 3, 3]);
+        assert_eq!(*sorted.range(..=5), [1, 3, 3, 5]);
+        assert_eq!(*sorted.range(2..6), [3, 3, 5]);
+        assert!(sorted.range((Included(6), Excluded(2))).is_empty());
+        assert!(sorted.range(4..5).is_empty());
+    }
+
+    #[test]
+    fn range_is_sorted_view() {
+        let sorted = Stable::sort([1, 3, 3, 5, 7]);
+        let view = sorted.range(3..);
+        assert_eq!(view.equal_range(&3), 0..2);
+        assert_eq!(*view.range(4..), [5, 7]);
+    }
+
+    #[test]
+    fn stored_rule() {
+        let sorted = Stable::sort_by(["bb", "a", "ccc"], |a, b| b.len().cmp(&a.len()));
+        assert_eq!(*sorted, ["ccc", "bb", "a"]);
+        assert_eq!(sorted.lower_bound(&"xx"), 1);
+        assert_eq!(sorted.upper_bound(&"xx"), 2);
+        assert!(sorted.contains(&"zz"));
+        assert!(!sorted.contains(&"zzzz"));
+    }
+
+    #[test]
+    fn random() {
+        let mut data: Vec<i32> = read_bench_data!("random");
+        let sorted = Stable::sort_slice(&mut data);
+        for value in [-1, 0, 17, 5000, 9999, 10_000] {
+            let expected = sorted.iter().filter(|x| **x < value).count();
+            assert_eq!(sorted.lower_bound(&value), expected);
+            let count = sorted.iter().filter(|x| **x == value).count();
+            assert_eq!(sorted.equal_range(&value).len(), count);
+            assert_eq!(sorted.contains(&value), count > 0);
+        }
+    }
+}
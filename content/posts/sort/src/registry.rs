@@ -0,0 +1,260 @@
+//! Every sorting algorithm of this crate with its properties,
+//! selectable by name at runtime.
+//!
+//! The tests sort the bench data and the benches time it with every entry.
+//!
+//! # Examples
+//!
+//! ```
+//! use sort::registry;
+//! let quick = registry::find("quick").unwrap();
+//! let mut array = [3, 1, 2];
+//! (quick.sort)(&mut array);
+//! assert_eq!(array, [1, 2, 3]);
+//! assert!(!quick.stable);
+//! ```
+
+use crate::adaptive::Adaptive;
+use crate::csort;
+use crate::instrument::{self, SortStats};
+use crate::{InPlaceMerge, ParallelMerge, SliceSolution, Stable};
+use std::fmt;
+
+/// Average time complexity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Complexity {
+    /// O(n)
+    Linear,
+    /// O(n log n)
+    Linearithmic,
+    /// O(n log^2 n), such as merging by rotations without a buffer
+    LogSquared,
+    /// O(n^2)
+    Quadratic,
+}
+
+impl fmt::Display for Complexity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Complexity::Linear => write!(f, "O(n)"),
+            Complexity::Linearithmic => write!(f, "O(n log n)"),
+            Complexity::LogSquared => write!(f, "O(n log^2 n)"),
+            Complexity::Quadratic => write!(f, "O(n^2)"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Algorithm {
+    pub name: &'static str,
+    /// Keeps equal elements in their original order,
+    /// checked by the stability tests of every sort.
+    pub stable: bool,
+    /// Needs no more than O(log n) extra memory.
+    pub in_place: bool,
+    pub parallel: bool,
+    pub complexity: Complexity,
+    pub sort: fn(&mut [i32]),
+    /// Count the work of one run, see [`crate::instrument`].
+    /// `None` for `adaptive`, which sorts only `i32` and can not track origins.
+    pub measure: Option<fn(&[i32]) -> SortStats>,
+}
+
+pub static ALGORITHMS: &[Algorithm] = &[
+    Algorithm {
+        name: "bubble",
+        stable: true,
+        in_place: true,
+        parallel: false,
+        complexity: Complexity::Quadratic,
+        sort: csort::bubble_sort,
+        measure: Some(|data| instrument::measure_c(csort::bubble_sort, data)),
+    },
+    Algorithm {
+        name: "selection",
+        stable: false,
+        in_place: true,
+        parallel: false,
+        complexity: Complexity::Quadratic,
+        sort: csort::selection_sort,
+        measure: Some(|data| instrument::measure_c(csort::selection_sort, data)),
+    },
+    Algorithm {
+        name: "insertion",
+        stable: true,
+        in_place: true,
+        parallel: false,
+        complexity: Complexity::Quadratic,
+        sort: csort::insertion_sort,
+        measure: Some(|data| instrument::measure_c(csort::insertion_sort, data)),
+    },
+    Algorithm {
+        name: "merge",
+        stable: true,
+        in_place: false,
+        parallel: false,
+        complexity: Complexity::Linearithmic,
+        sort: |array| csort::merge_sort(array).unwrap(),
+        measure: Some(|data| {
+            instrument::measure_c(|array| csort::merge_sort(array).unwrap(), data)
+        }),
+    },
+    Algorithm {
+        name: "merge_parallel",
+        stable: true,
+        in_place: false,
+        parallel: true,
+        complexity: Complexity::Linearithmic,
+        sort: |array| csort::merge_sort_parallel(array).unwrap(),
+        measure: Some(|data| {
+            instrument::measure_c(|array| csort::merge_sort_parallel(array).unwrap(), data)
+        }),
+    },
+    Algorithm {
+        name: "radix_lsd",
+        stable: true,
+        in_place: false,
+        parallel: false,
+        complexity: Complexity::Linear,
+        sort: |array| csort::radix_lsd_sort(array).unwrap(),
+        measure: Some(|data| {
+            instrument::measure_c(|array| csort::radix_lsd_sort(array).unwrap(), data)
+        }),
+    },
+    Algorithm {
+        name: "cstd_qsort",
+        stable: false,
+        in_place: true,
+        parallel: false,
+        complexity: Complexity::Linearithmic,
+        sort: csort::cstd_qsort,
+        // qsort moves the elements inside the C library
+        measure: Some(|data| SortStats {
+            writes: None,
+            swaps: None,
+            ..instrument::measure_c(csort::cstd_qsort, data)
+        }),
+    },
+    Algorithm {
+        name: "quick",
+        stable: false,
+        in_place: true,
+        parallel: false,
+        complexity: Complexity::Linearithmic,
+        sort: csort::quick_sort,
+        measure: Some(|data| instrument::measure_c(csort::quick_sort, data)),
+    },
+    Algorithm {
+        name: "rust_stable",
+        stable: true,
+        in_place: false,
+        parallel: false,
+        complexity: Complexity::Linearithmic,
+        sort: |array| _ = Stable::sort_slice(array),
+        measure: Some(|data| instrument::measure(Stable, data)),
+    },
+    Algorithm {
+        name: "rust_merge_parallel",
+        stable: true,
+        in_place: false,
+        parallel: true,
+        complexity: Complexity::Linearithmic,
+        sort: |array| _ = ParallelMerge::sort_slice(array),
+        measure: Some(|data| instrument::measure(ParallelMerge, data)),
+    },
+    Algorithm {
+        name: "rust_merge_in_place",
+        stable: true,
+        in_place: true,
+        parallel: false,
+        complexity: Complexity::LogSquared,
+        sort: |array| _ = InPlaceMerge::sort_slice(array),
+        measure: Some(|data| instrument::measure(InPlaceMerge, data)),
+    },
+    Algorithm {
+        name: "adaptive",
+        stable: false,
+        in_place: false,
+        parallel: false,
+        complexity: Complexity::Linearithmic,
+        sort: |array| _ = Adaptive::sort_slice(array),
+        measure: None,
+    },
+];
+
+/// Find an algorithm by its name.
+pub fn find(name: &str) -> Option<&'static Algorithm> {
+    ALGORITHMS.iter().find(|algorithm| algorithm.name == name)
+}
+
+/// Names of all algorithms, in registration order.
+pub fn names() -> impl Iterator<Item = &'static str> {
+    ALGORITHMS.iter().map(|algorithm| algorithm.name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::bench_data;
+
+    #[test]
+    fn unique_names() {
+        let mut names: Vec<_> = names().collect();
+        names.sort();
+        names.dedup();
+        assert_eq!(names.len(), ALGORITHMS.len());
+    }
+
+    #[test]
+    fn find_by_name() {
+        ALGORITHMS
+            .iter()
+            .for_each(|algorithm| assert_eq!(find(algorithm.name).unwrap().name, algorithm.name));
+        assert!(find("bogo").is_none());
+    }
+
+    #[test]
+    fn bench_every_algorithm() {
+        let mut benched = bench::BENCHED.to_vec();
+        benched.sort();
+        let mut names: Vec<_> = names().collect();
+        names.sort();
+        assert_eq!(benched, names);
+    }
+
+    #[test]
+    fn sort_bench_data() {
+        for (name, data) in bench_data() {
+            let mut expected = data.clone();
+            expected.sort();
+
+            for algorithm in ALGORITHMS {
+                let mut array = data.clone();
+                (algorithm.sort)(&mut array);
+                assert_eq!(array, expected, "{} on {}", algorithm.name, name);
+            }
+        }
+    }
+
+    mod bench {
+        use super::*;
+
+        /// A module of benches for every name, sorting the given input.
+        macro_rules! bench_registry {
+            ($($input:ident: $($name:ident),+;)+) => {
+                pub const BENCHED: &[&str] = &[$($(stringify!($name)),+),+];
+                $($(
+                    mod $name {
+                        use super::*;
+                        crate::bench_all!(find(stringify!($name)).unwrap().sort, $input);
+                    }
+                )+)+
+            };
+        }
+
+        bench_registry! {
+            Reused: bubble, selection, insertion, merge, merge_parallel, radix_lsd, cstd_qsort, quick, rust_stable;
+            Fresh: rust_merge_parallel, rust_merge_in_place, adaptive;
+        }
+    }
+}
@@ -0,0 +1,354 @@
+//! Selection of the n-th smallest element without sorting everything.
+//!
+//! Quickselect with a median-of-three pivot, which falls back to the
+//! median-of-medians pivot after a few unbalanced partitions,
+//! so the worst case stays linear.
+//!
+//! # Examples
+//!
+//! ```
+//! use sort::select::select_nth_unstable;
+//! let mut vec = vec![5, 1, 4, 2, 3];
+//! let (less, median, greater) = select_nth_unstable(&mut vec, 2);
+//! assert_eq!(*median, 3);
+//! assert!(less.iter().all(|x| *x <= 3));
+//! assert!(greater.iter().all(|x| *x >= 3));
+//! ```
+
+/// Slices not longer than this are sorted by insertion.
+const SMALL: usize = 16;
+
+/// Partitions leaving more than 3/4 of the elements on one side
+/// before switching to median of medians.
+const BAD_PARTITIONS: usize = 4;
+
+/// Reorder `slice` so that the element at `n` is the one that would be there
+/// if sorted, no greater elements are before it and no less elements after it.
+///
+/// Returns the elements before `n`, the element at `n` and the elements after.
+///
+/// # Panics
+///
+/// Panics if `n >= slice.len()`.
+pub fn select_nth_unstable_by<T, F>(
+    slice: &mut [T],
+    n: usize,
+    is_less: F,
+) -> (&mut [T], &mut T, &mut [T])
+where
+    F: FnMut(&T, &T) -> bool,
+{
+    assert!(
+        n < slice.len(),
+        "select index {} out of range for length {}",
+        n,
+        slice.len()
+    );
+
+    let mut is_less = is_less;
+    select(slice, n, &mut is_less);
+
+    let (less, rest) = slice.split_at_mut(n);
+    let (nth, greater) = rest.split_first_mut().unwrap();
+    (less, nth, greater)
+}
+
+pub fn select_nth_unstable<T: Ord>(slice: &mut [T], n: usize) -> (&mut [T], &mut T, &mut [T]) {
+    select_nth_unstable_by(slice, n, T::lt)
+}
+
+fn select<T, F>(slice: &mut [T], n: usize, is_less: &mut F)
+where
+    F: FnMut(&T, &T) -> bool,
+{
+    let (mut slice, mut n) = (slice, n);
+    let mut bad_partitions = 0;
+
+    loop {
+        let len = slice.len();
+        if len <= SMALL {
+            insertion_sort(slice, is_less);
+            return;
+        }
+
+        let pivot = if bad_partitions < BAD_PARTITIONS {
+            median_of_three(slice, is_less)
+        } else {
+            median_of_medians(slice, is_less)
+        };
+
+        let equal = partition(slice, pivot, is_less);
+        if equal.start.max(len - equal.end) > len / 4 * 3 {
+            bad_partitions += 1;
+        }
+
+        if n < equal.start {
+            slice = &mut slice[..equal.start];
+        } else if n >= equal.end {
+            slice = &mut slice[equal.end..];
+            n -= equal.end;
+        } else {
+            return;
+        }
+    }
+}
+
+pub(crate) fn insertion_sort<T, F>(slice: &mut [T], is_less: &mut F)
+where
+    F: FnMut(&T, &T) -> bool,
+{
+    for i in 1..slice.len() {
+        let mut j = i;
+        while j > 0 && is_less(&slice[j], &slice[j - 1]) {
+            slice.swap(j - 1, j);
+            j -= 1;
+        }
+    }
+}
+
+/// Quick sort sharing the pivots and the partition of the selection,
+/// so it is O(n log n) in the worst case and linear for equal elements.
+pub(crate) fn quick_sort<T, F>(slice: &mut [T], is_less: &mut F)
+where
+    F: FnMut(&T, &T) -> bool,
+{
+    let mut slice = slice;
+    let mut bad_partitions = 0;
+
+    loop {
+        let len = slice.len();
+        if len <= SMALL {
+            insertion_sort(slice, is_less);
+            return;
+        }
+
+        let pivot = if bad_partitions < BAD_PARTITIONS {
+            median_of_three(slice, is_less)
+        } else {
+            median_of_medians(slice, is_less)
+        };
+
+        let equal = partition(slice, pivot, is_less);
+        if equal.start.max(len - equal.end) > len / 4 * 3 {
+            bad_partitions += 1;
+        }
+
+        // recurse into the shorter side, loop on the longer one
+        let (less, rest) = slice.split_at_mut(equal.start);
+        let greater = &mut rest[equal.len()..];
+        if less.len() < greater.len() {
+            quick_sort(less, is_less);
+            slice = greater;
+        } else {
+            quick_sort(greater, is_less);
+            slice = less;
+        }
+    }
+}
+
+/// Index of the median of the first, middle and last elements.
+fn median_of_three<T, F>(slice: &[T], is_less: &mut F) -> usize
+where
+    F: FnMut(&T, &T) -> bool,
+{
+    let (a, b, c) = (0, slice.len() / 2, slice.len() - 1);
+    let (x, y, z) = (&slice[a], &slice[b], &slice[c]);
+    if is_less(x, y) {
+        if is_less(y, z) {
+            b
+        } else if is_less(x, z) {
+            c
+        } else {
+            a
+        }
+    } else if is_less(x, z) {
+        a
+    } else if is_less(y, z) {
+        c
+    } else {
+        b
+    }
+}
+
+/// Index of the median of the medians of groups of five.
+fn median_of_medians<T, F>(slice: &mut [T], is_less: &mut F) -> usize
+where
+    F: FnMut(&T, &T) -> bool,
+{
+    let groups = slice.len() / 5;
+    for group in 0..groups {
+        let five = &mut slice[group * 5..group * 5 + 5];
+        insertion_sort(five, is_less);
+        slice.swap(group, group * 5 + 2);
+    }
+
+    // the medians are at the front now
+    select(&mut slice[..groups], groups / 2, is_less);
+    groups / 2
+}
+
+/// Three-way partition around `slice[pivot]`.
+///
+/// Returns the indices of the elements equal to the pivot,
+/// the less elements are before them and the greater elements after.
+fn partition<T, F>(slice: &mut [T], pivot: usize, is_less: &mut F) -> std::ops::Range<usize>
+where
+    F: FnMut(&T, &T) -> bool,
+{
+    slice.swap(0, pivot);
+    let (pivot, rest) = slice.split_first_mut().unwrap();
+
+    let (mut lt, mut i, mut gt) = (0, 0, rest.len());
+    while i < gt {
+        if is_less(&rest[i], pivot) {
+            rest.swap(lt, i);
+            lt += 1;
+            i += 1;
+        } else if is_less(pivot, &rest[i]) {
+            gt -= 1;
+            rest.swap(i, gt);
+        } else {
+            i += 1;
+        }
+    }
+
+    // rest[..lt] is less, rest[lt..gt] is equal, rest[gt..] is greater
+    slice.swap(0, lt);
+    lt..gt + 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::instrument::counting;
+    use crate::test_utils::bench_data;
+    use std::cell::Cell;
+
+    fn test_select(data: &[i32], n: usize) {
+        let mut expected = data.to_vec();
+        expected.sort();
+
+        let mut vec = data.to_vec();
+        let (less, nth, greater) = select_nth_unstable(&mut vec, n);
+        assert_eq!(*nth, expected[n]);
+        assert!(less.iter().all(|x| x <= nth));
+        assert!(greater.iter().all(|x| x >= nth));
+
+        vec.sort();
+        assert_eq!(vec, expected);
+    }
+
+    #[test]
+    fn small() {
+        test_select(&[1], 0);
+        test_select(&[2, 1], 0);
+        test_select(&[2, 1], 1);
+        for n in 0..7 {
+            test_select(&[3, 6, 1, 0, 5, 2, 4], n);
+        }
+    }
+
+    #[test]
+    fn duplicates() {
+        let data: Vec<_> = (0..1000).map(|x| x % 3).collect();
+        for n in [0, 333, 334, 500, 999] {
+            test_select(&data, n);
+        }
+        test_select(&[7; 100], 50);
+    }
+
+    #[test]
+    fn bench_data_select() {
+        for (_, data) in bench_data() {
+            let len = data.len();
+            for n in [0, len / 4, len / 2, len - 1] {
+                test_select(&data, n);
+            }
+        }
+    }
+
+    #[test]
+    fn median_of_medians_fallback() {
+        for (_, data) in bench_data() {
+            let len = data.len();
+            let mut vec = data.clone();
+            let mut is_less = i32::lt;
+            let pivot = median_of_medians(&mut vec, &mut is_less);
+
+            // at least 3/10 of the elements on each side
+            let less = vec.iter().filter(|x| **x < vec[pivot]).count();
+            let greater = vec.iter().filter(|x| **x > vec[pivot]).count();
+            assert!(less <= len / 10 * 7 + 6 && greater <= len / 10 * 7 + 6);
+        }
+    }
+
+    #[test]
+    fn linear_comparisons() {
+        // organ pipes make median of three pick poor pivots
+        let len = 10_000;
+        let mut organ_pipe: Vec<i32> = (0..len / 2).chain((0..len / 2).rev()).collect();
+        let mut sorted: Vec<i32> = (0..len).collect();
+
+        for data in [&mut organ_pipe, &mut sorted] {
+            let counter = Cell::new(0);
+            select_nth_unstable_by(data, len as usize / 2, counting(i32::lt, &counter));
+            assert!(counter.get() < 30 * len as usize, "{}", counter.get());
+        }
+    }
+
+    #[test]
+    fn quick() {
+        let len = 10_000;
+        let organ_pipe: Vec<i32> = (0..len / 2).chain((0..len / 2).rev()).collect();
+        for data in bench_data()
+            .map(|(_, data)| data)
+            .into_iter()
+            .chain([organ_pipe])
+        {
+            let mut expected = data.clone();
+            expected.sort();
+            let mut vec = data;
+            quick_sort(&mut vec, &mut i32::lt);
+            assert_eq!(vec, expected);
+        }
+    }
+
+    #[test]
+    fn partial_sort() {
+        use crate::{SliceSolution, Solution, Stable};
+
+        let array = [9, 3, 5, 1, 7, 4, 6, 2, 8, 0];
+        assert!(Stable::partial_sort::<0>(array).is_empty());
+        assert_eq!(*Stable::partial_sort::<4>(array), [0, 1, 2, 3]);
+        assert_eq!(
+            *Stable::partial_sort::<10>(array),
+            [0, 1, 2, 3, 4, 5, 6, 7, 8, 9]
+        );
+        let top = Stable::top_k::<3>(array);
+        assert!(top.force_check());
+        assert_eq!(*top, [9, 8, 7]);
+
+        for (_, data) in bench_data() {
+            let mut expected = data.clone();
+            expected.sort();
+
+            let mut vec = data.clone();
+            let sorted = Stable::partial_sort_slice(&mut vec, 100);
+            assert_eq!(sorted.take(), &expected[..100]);
+
+            let mut vec = data.clone();
+            let top = Stable::top_k_slice(&mut vec, 100);
+            assert!(top.force_check());
+            assert!(top.iter().eq(expected.iter().rev().take(100)));
+        }
+
+        let mut vec = vec![2, 1];
+        assert_eq!(Stable::partial_sort_slice(&mut vec, 5).take(), [1, 2]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn out_of_range() {
+        select_nth_unstable(&mut [1, 2, 3], 3);
+    }
+}
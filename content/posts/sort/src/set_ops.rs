@@ -0,0 +1,398 @@
+//! Linear time set operations and deduplication on sorted data,
+//! built on [`merge_join_by`].
+//!
+//! Duplicated elements are treated as a multiset: an element repeated
+//! `m` times on the left and `n` times on the right appears
+//! `max(m, n)` times in the union, `min(m, n)` times in the intersection,
+//! `m - n` times in the difference and `|m - n|` times in the symmetric difference.
+//! Elements taken from both sides come from the left one.
+//!
+//! The result keeps the rule of the left side. The right side is
+//! re-sorted by that rule first if its own rule orders it differently.
+
+use crate::merge_two_sorted::{ensure_sorted_by, merge_join_by, Merged};
+use crate::{Sorted, SortedSlice, SortedVec};
+use std::cell::RefCell;
+
+fn union<T>(merged: Merged<T>) -> Option<T> {
+    match merged {
+        Merged::Left(x) | Merged::Right(x) | Merged::Both(x, _) => Some(x),
+    }
+}
+
+fn intersection<T>(merged: Merged<T>) -> Option<T> {
+    match merged {
+        Merged::Both(x, _) => Some(x),
+        _ => None,
+    }
+}
+
+fn difference<T>(merged: Merged<T>) -> Option<T> {
+    match merged {
+        Merged::Left(x) => Some(x),
+        _ => None,
+    }
+}
+
+fn symmetric_difference<T>(merged: Merged<T>) -> Option<T> {
+    match merged {
+        Merged::Left(x) | Merged::Right(x) => Some(x),
+        Merged::Both(..) => None,
+    }
+}
+
+fn join<T, B, F>(
+    a: impl IntoIterator<Item = T>,
+    b: B,
+    rule_is_less: F,
+    pick: fn(Merged<T>) -> Option<T>,
+) -> SortedVec<T, F>
+where
+    B: AsMut<[T]> + IntoIterator<Item = T>,
+    F: FnMut(&T, &T) -> bool,
+{
+    let mut rule_is_less = rule_is_less;
+    let mut b = b;
+    ensure_sorted_by(b.as_mut(), &mut rule_is_less);
+    let vec = merge_join_by(a, b, &mut rule_is_less)
+        .filter_map(pick)
+        .collect();
+    unsafe { SortedVec::uncheck_from_vec(vec, rule_is_less) }
+}
+
+fn dedup<T, F>(vec: Vec<T>, rule_is_less: F) -> SortedVec<T, F>
+where
+    F: FnMut(&T, &T) -> bool,
+{
+    let mut vec = vec;
+    let mut rule_is_less = rule_is_less;
+    vec.dedup_by(|next, prev| !rule_is_less(prev, next));
+    unsafe { SortedVec::uncheck_from_vec(vec, rule_is_less) }
+}
+
+fn runs<'s, 'r, T, F>(
+    slice: &'s [T],
+    rule_is_less: &'r RefCell<F>,
+) -> impl Iterator<Item = &'s [T]> + 'r
+where
+    's: 'r,
+    F: FnMut(&T, &T) -> bool,
+{
+    slice.chunk_by(move |prev, next| !(rule_is_less.borrow_mut())(prev, next))
+}
+
+impl<T, const N: usize, F: FnMut(&T, &T) -> bool> Sorted<T, N, F> {
+    /// # Examples
+    ///
+    /// ```
+    /// use sort::{Solution, Stable};
+    /// let a = Stable::sort([1, 3, 3, 5]);
+    /// let b = Stable::sort([2, 3, 5, 5]);
+    /// assert_eq!(*a.union(b), [1, 2, 3, 3, 5, 5]);
+    /// ```
+    pub fn union<const M: usize, G>(self, other: Sorted<T, M, G>) -> SortedVec<T, F>
+    where
+        G: FnMut(&T, &T) -> bool,
+    {
+        let rule_is_less = self.rule_is_less.into_inner();
+        join(self.array, other.array, rule_is_less, union)
+    }
+
+    /// # Examples
+    ///
+    /// ```
+    /// use sort::{Solution, Stable};
+    /// let a = Stable::sort([1, 3, 3, 5]);
+    /// let b = Stable::sort([2, 3, 5, 5]);
+    /// assert_eq!(*a.intersection(b), [3, 5]);
+    /// ```
+    pub fn intersection<const M: usize, G>(self, other: Sorted<T, M, G>) -> SortedVec<T, F>
+    where
+        G: FnMut(&T, &T) -> bool,
+    {
+        let rule_is_less = self.rule_is_less.into_inner();
+        join(self.array, other.array, rule_is_less, intersection)
+    }
+
+    /// # Examples
+    ///
+    /// ```
+    /// use sort::{Solution, Stable};
+    /// let a = Stable::sort([1, 3, 3, 5]);
+    /// let b = Stable::sort([2, 3, 5, 5]);
+    /// assert_eq!(*a.difference(b), [1, 3]);
+    /// ```
+    pub fn difference<const M: usize, G>(self, other: Sorted<T, M, G>) -> SortedVec<T, F>
+    where
+        G: FnMut(&T, &T) -> bool,
+    {
+        let rule_is_less = self.rule_is_less.into_inner();
+        join(self.array, other.array, rule_is_less, difference)
+    }
+
+    /// # Examples
+    ///
+    /// ```
+    /// use sort::{Solution, Stable};
+    /// let a = Stable::sort([1, 3, 3, 5]);
+    /// let b = Stable::sort([2, 3, 5, 5]);
+    /// assert_eq!(*a.symmetric_difference(b), [1, 2, 3, 5]);
+    /// ```
+    pub fn symmetric_difference<const M: usize, G>(self, other: Sorted<T, M, G>) -> SortedVec<T, F>
+    where
+        G: FnMut(&T, &T) -> bool,
+    {
+        let rule_is_less = self.rule_is_less.into_inner();
+        join(self.array, other.array, rule_is_less, symmetric_difference)
+    }
+
+    /// Keep the first element of every run of equal elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sort::{Solution, Stable};
+    /// let a = Stable::sort([3, 1, 3, 5, 1]);
+    /// assert_eq!(*a.dedup(), [1, 3, 5]);
+    /// ```
+    pub fn dedup(self) -> SortedVec<T, F> {
+        dedup(Vec::from(self.array), self.rule_is_less.into_inner())
+    }
+
+    /// Group runs of equal elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sort::{Solution, Stable};
+    /// let a = Stable::sort([3, 1, 3, 5, 3]);
+    /// let runs: Vec<_> = a.runs().map(|run| (run[0], run.len())).collect();
+    /// assert_eq!(runs, [(1, 1), (3, 3), (5, 1)]);
+    /// ```
+    pub fn runs(&self) -> impl Iterator<Item = &[T]> {
+        runs(&self.array, &self.rule_is_less)
+    }
+}
+
+impl<'a, T: Clone, F: FnMut(&T, &T) -> bool> SortedSlice<'a, T, F> {
+    /// See [`Sorted::union`].
+    pub fn union<G>(self, other: SortedSlice<'_, T, G>) -> SortedVec<T, F>
+    where
+        G: FnMut(&T, &T) -> bool,
+    {
+        let (a, b) = (self.slice.iter().cloned(), other.slice.to_vec());
+        let rule_is_less = self.rule_is_less.into_inner();
+        join(a, b, rule_is_less, union)
+    }
+
+    /// See [`Sorted::intersection`].
+    pub fn intersection<G>(self, other: SortedSlice<'_, T, G>) -> SortedVec<T, F>
+    where
+        G: FnMut(&T, &T) -> bool,
+    {
+        let (a, b) = (self.slice.iter().cloned(), other.slice.to_vec());
+        let rule_is_less = self.rule_is_less.into_inner();
+        join(a, b, rule_is_less, intersection)
+    }
+
+    /// See [`Sorted::difference`].
+    pub fn difference<G>(self, other: SortedSlice<'_, T, G>) -> SortedVec<T, F>
+    where
+        G: FnMut(&T, &T) -> bool,
+    {
+        let (a, b) = (self.slice.iter().cloned(), other.slice.to_vec());
+        let rule_is_less = self.rule_is_less.into_inner();
+        join(a, b, rule_is_less, difference)
+    }
+
+    /// See [`Sorted::symmetric_difference`].
+    pub fn symmetric_difference<G>(self, other: SortedSlice<'_, T, G>) -> SortedVec<T, F>
+    where
+        G: FnMut(&T, &T) -> bool,
+    {
+        let (a, b) = (self.slice.iter().cloned(), other.slice.to_vec());
+        let rule_is_less = self.rule_is_less.into_inner();
+        join(a, b, rule_is_less, symmetric_difference)
+    }
+
+    /// See [`Sorted::dedup`].
+    pub fn dedup(self) -> SortedVec<T, F> {
+        dedup(self.slice.to_vec(), self.rule_is_less.into_inner())
+    }
+}
+
+impl<'a, T, F: FnMut(&T, &T) -> bool> SortedSlice<'a, T, F> {
+    /// See [`Sorted::runs`].
+    pub fn runs(&self) -> impl Iterator<Item = &'a [T]> + '_ {
+        runs(self.slice, &self.rule_is_less)
+    }
+}
+
+impl<T, F: FnMut(&T, &T) -> bool> SortedVec<T, F> {
+    /// See [`Sorted::union`].
+    pub fn union<G>(self, other: SortedVec<T, G>) -> SortedVec<T, F>
+    where
+        G: FnMut(&T, &T) -> bool,
+    {
+        let rule_is_less = self.rule_is_less.into_inner();
+        join(self.vec, other.vec, rule_is_less, union)
+    }
+
+    /// See [`Sorted::intersection`].
+    pub fn intersection<G>(self, other: SortedVec<T, G>) -> SortedVec<T, F>
+    where
+        G: FnMut(&T, &T) -> bool,
+    {
+        let rule_is_less = self.rule_is_less.into_inner();
+        join(self.vec, other.vec, rule_is_less, intersection)
+    }
+
+    /// See [`Sorted::difference`].
+    pub fn difference<G>(self, other: SortedVec<T, G>) -> SortedVec<T, F>
+    where
+        G: FnMut(&T, &T) -> bool,
+    {
+        let rule_is_less = self.rule_is_less.into_inner();
+        join(self.vec, other.vec, rule_is_less, difference)
+    }
+
+    /// See [`Sorted::symmetric_difference`].
+    pub fn symmetric_difference<G>(self, other: SortedVec<T, G>) -> SortedVec<T, F>
+    where
+        G: FnMut(&T, &T) -> bool,
+    {
+        let rule_is_less = self.rule_is_less.into_inner();
+        join(self.vec, other.vec, rule_is_less, symmetric_difference)
+    }
+
+    /// See [`Sorted::dedup`].
+    pub fn dedup(self) -> SortedVec<T, F> {
+        dedup(self.vec, self.rule_is_less.into_inner())
+    }
+
+    /// See [`Sorted::runs`].
+    pub fn runs(&self) -> impl Iterator<Item = &[T]> {
+        runs(&self.vec, &self.rule_is_less)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{read_bench_data, SliceSolution, Solution, Stable};
+    use std::collections::{BTreeMap, BTreeSet};
+
+    fn count(slice: &[i32]) -> BTreeMap<i32, usize> {
+        let mut map = BTreeMap::new();
+        slice.iter().for_each(|x| *map.entry(*x).or_default() += 1);
+        map
+    }
+
+    fn expand(map: BTreeMap<i32, usize>) -> Vec<i32> {
+        map.into_iter()
+            .flat_map(|(x, n)| std::iter::repeat_n(x, n))
+            .collect()
+    }
+
+    #[test]
+    fn empty() {
+        let a = Stable::sort([] as [i32; 0]);
+        let b = Stable::sort([1, 2]);
+        assert_eq!(*a.union(b), [1, 2]);
+        let a = Stable::sort([1, 2]);
+        let b = Stable::sort([] as [i32; 0]);
+        assert!(a.intersection(b).is_empty());
+        assert!(Stable::sort([] as [i32; 0]).dedup().is_empty());
+        assert_eq!(Stable::sort([] as [i32; 0]).runs().count(), 0);
+    }
+
+    #[test]
+    fn keep_left() {
+        let a = Stable::sort_by_key([(1, 'a'), (2, 'a')], |x| x.0);
+        let b = Stable::sort_by_key([(2, 'b'), (3, 'b')], |x| x.0);
+        assert_eq!(*a.union(b), [(1, 'a'), (2, 'a'), (3, 'b')]);
+        let a = Stable::sort_by_key([(1, 'a'), (1, 'b'), (2, 'a')], |x| x.0);
+        assert_eq!(*a.dedup(), [(1, 'a'), (2, 'a')]);
+    }
+
+    #[test]
+    fn keep_rule() {
+        let a = Stable::sort_by([1, 5, 3], |a, b| b.cmp(a));
+        let b = Stable::sort_by([4, 3], |a, b| b.cmp(a));
+        let union = a.union(b);
+        assert_eq!(*union, [5, 4, 3, 1]);
+        assert_eq!(union.as_sorted_slice().lower_bound(&3), 2);
+    }
+
+    #[test]
+    fn other_rule() {
+        let a = Stable::sort_method([1, 3, 5], i32::lt);
+        let b = Stable::sort_method([2, 3, 6], i32::gt);
+        let union = a.union(b);
+        assert_eq!(*union, [1, 2, 3, 5, 6]);
+        assert!(union.force_check());
+
+        let a = Stable::sort_method([1, 3, 5], i32::lt);
+        let b = Stable::sort_method([5, 1, 6], i32::gt);
+        assert_eq!(*a.intersection(b), [1, 5]);
+
+        let mut a = vec![1, 3, 5];
+        let mut b = vec![3, 6];
+        let a = Stable::sort_slice_method(&mut a, i32::lt);
+        let b = Stable::sort_slice_method(&mut b, i32::gt);
+        assert_eq!(*a.symmetric_difference(b), [1, 5, 6]);
+
+        let a = Stable::sort_method([1, 3, 5], i32::lt).dedup();
+        let b = Stable::sort_method([3, 4], i32::gt).dedup();
+        assert_eq!(*a.difference(b), [1, 5]);
+    }
+
+    #[test]
+    fn slice() {
+        let mut a = vec![5, 3, 1, 3];
+        let mut b = vec![3, 4];
+        let a = Stable::sort_slice(&mut a);
+        let b = Stable::sort_slice(&mut b);
+        assert_eq!(*a.symmetric_difference(b), [1, 3, 4, 5]);
+    }
+
+    #[test]
+    fn chain() {
+        let a = Stable::sort([1, 2, 3, 4]);
+        let b = Stable::sort([3, 4, 5]);
+        let c = Stable::sort([4, 5, 6]);
+        assert_eq!(*a.union(b).intersection(c.dedup()), [4, 5]);
+    }
+
+    #[test]
+    fn bench_data() {
+        let mut a: Vec<i32> = read_bench_data!("gaussian-with-noise");
+        let mut b: Vec<i32> = read_bench_data!("low-sample-sin-with-noise");
+        let (ca, cb) = (count(&a), count(&b));
+        let keys: BTreeSet<i32> = ca.keys().chain(cb.keys()).copied().collect();
+        let multiset = |f: fn(usize, usize) -> usize| {
+            let get = |map: &BTreeMap<i32, usize>, x| map.get(x).copied().unwrap_or(0);
+            expand(
+                keys.iter()
+                    .map(|x| (*x, f(get(&ca, x), get(&cb, x))))
+                    .collect(),
+            )
+        };
+
+        let (sa, sb) = (Stable::sort_slice(&mut a), Stable::sort_slice(&mut b));
+        assert_eq!(*sa.union(sb), multiset(usize::max));
+        let (sa, sb) = (Stable::sort_slice(&mut a), Stable::sort_slice(&mut b));
+        assert_eq!(*sa.intersection(sb), multiset(usize::min));
+        let (sa, sb) = (Stable::sort_slice(&mut a), Stable::sort_slice(&mut b));
+        assert_eq!(*sa.difference(sb), multiset(usize::saturating_sub));
+        let (sa, sb) = (Stable::sort_slice(&mut a), Stable::sort_slice(&mut b));
+        assert_eq!(*sa.symmetric_difference(sb), multiset(usize::abs_diff));
+        assert_eq!(
+            *Stable::sort_slice(&mut a).dedup(),
+            ca.keys().copied().collect::<Vec<_>>()
+        );
+
+        let sorted = Stable::sort_slice(&mut a);
+        let runs: BTreeMap<_, _> = sorted.runs().map(|run| (run[0], run.len())).collect();
+        assert_eq!(runs, ca);
+    }
+}
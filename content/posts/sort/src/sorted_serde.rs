@@ -0,0 +1,126 @@
+//! Serialize sorted data as a plain sequence, and check the order again
+//! when deserializing instead of trusting the input.
+
+use crate::{verify_by, Sorted, SortedSlice, SortedVec};
+use serde::de::{Deserialize, Deserializer, Error};
+use serde::ser::{Serialize, Serializer};
+use std::fmt::Debug;
+
+impl<T: Serialize, const N: usize, F: FnMut(&T, &T) -> bool> Serialize for Sorted<T, N, F> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(&self.array)
+    }
+}
+
+impl<T: Serialize, F: FnMut(&T, &T) -> bool> Serialize for SortedSlice<'_, T, F> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.slice)
+    }
+}
+
+impl<T: Serialize, F: FnMut(&T, &T) -> bool> Serialize for SortedVec<T, F> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(&self.vec)
+    }
+}
+
+impl<T, const N: usize, F: FnMut(&T, &T) -> bool> Sorted<T, N, F> {
+    /// Deserialize a sequence of `N` elements sorted by `rule_is_less`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sort::Sorted;
+    /// let mut json = serde_json::Deserializer::from_str("[1, 2, 2, 3]");
+    /// let sorted: Sorted<i32, 4, _> = Sorted::deserialize_with(&mut json, i32::lt).unwrap();
+    /// assert_eq!(*sorted, [1, 2, 2, 3]);
+    ///
+    /// let mut json = serde_json::Deserializer::from_str("[1, 3, 2, 4]");
+    /// let error = Sorted::<i32, 4, _>::deserialize_with(&mut json, i32::lt).err().unwrap();
+    /// assert_eq!(error.to_string(), "not sorted at index 2: 2 is less than the previous 3");
+    /// ```
+    pub fn deserialize_with<'de, D>(deserializer: D, rule_is_less: F) -> Result<Self, D::Error>
+    where
+        T: Deserialize<'de> + Debug,
+        D: Deserializer<'de>,
+    {
+        let vec = Vec::<T>::deserialize(deserializer)?;
+        let array: [T; N] = vec.try_into().map_err(|vec: Vec<T>| {
+            D::Error::invalid_length(vec.len(), &format!("an array of length {}", N).as_str())
+        })?;
+
+        let mut rule_is_less = rule_is_less;
+        verify_by(&array, &mut rule_is_less).map_err(D::Error::custom)?;
+        Ok(unsafe { Sorted::uncheck_from_array(array, rule_is_less) })
+    }
+}
+
+impl<T, F: FnMut(&T, &T) -> bool> SortedVec<T, F> {
+    /// See [`Sorted::deserialize_with`].
+    pub fn deserialize_with<'de, D>(deserializer: D, rule_is_less: F) -> Result<Self, D::Error>
+    where
+        T: Deserialize<'de> + Debug,
+        D: Deserializer<'de>,
+    {
+        let vec = Vec::<T>::deserialize(deserializer)?;
+
+        let mut rule_is_less = rule_is_less;
+        verify_by(&vec, &mut rule_is_less).map_err(D::Error::custom)?;
+        Ok(unsafe { SortedVec::uncheck_from_vec(vec, rule_is_less) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{read_bench_data, SliceSolution, Solution, Sorted, SortedVec, Stable};
+    use serde_json::Deserializer;
+
+    #[test]
+    fn serialize() {
+        let sorted = Stable::sort([3, 1, 2]);
+        assert_eq!(serde_json::to_string(&sorted).unwrap(), "[1,2,3]");
+        let mut vec = vec!["b", "a"];
+        let sorted = Stable::sort_slice(&mut vec);
+        assert_eq!(serde_json::to_string(&sorted).unwrap(), r#"["a","b"]"#);
+        let sorted = SortedVec::<i32, _>::new(i32::lt);
+        assert_eq!(serde_json::to_string(&sorted).unwrap(), "[]");
+    }
+
+    #[test]
+    fn wrong_length() {
+        let mut json = Deserializer::from_str("[1, 2, 3]");
+        let error = Sorted::<i32, 2, _>::deserialize_with(&mut json, i32::lt)
+            .err()
+            .unwrap();
+        assert_eq!(
+            error.to_string(),
+            "invalid length 3, expected an array of length 2"
+        );
+    }
+
+    #[test]
+    fn stored_rule() {
+        let mut json = Deserializer::from_str("[3, 2, 2, 1]");
+        let sorted = SortedVec::deserialize_with(&mut json, |a: &i32, b: &i32| a > b).unwrap();
+        assert_eq!(sorted.lower_bound(&2), 1);
+
+        let mut json = Deserializer::from_str("[1, 2]");
+        assert!(SortedVec::deserialize_with(&mut json, |a: &i32, b: &i32| a > b).is_err());
+    }
+
+    #[test]
+    fn round_trip() {
+        let data: [i32; 10000] = read_bench_data!("random");
+        let json = serde_json::to_string(&Stable::sort(data)).unwrap();
+
+        let mut de = Deserializer::from_str(&json);
+        let sorted: Sorted<i32, 10000, _> = Sorted::deserialize_with(&mut de, i32::lt).unwrap();
+        let mut expected = data;
+        expected.sort();
+        assert_eq!(sorted.take(), expected);
+
+        let json = serde_json::to_string(data.as_slice()).unwrap();
+        let mut de = Deserializer::from_str(&json);
+        assert!(SortedVec::deserialize_with(&mut de, i32::lt).is_err());
+    }
+}
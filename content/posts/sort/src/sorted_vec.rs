@@ -0,0 +1,201 @@
+//! Keep a [`SortedVec`] sorted while inserting and removing elements.
+
+use crate::merge_two_sorted::merge_iter_by;
+use crate::SortedVec;
+use std::cmp::Ordering;
+use std::ops::RangeBounds;
+
+impl<T, F: FnMut(&T, &T) -> bool> SortedVec<T, F> {
+    /// An empty vec sorted by `rule_is_less`.
+    pub fn new(rule_is_less: F) -> Self {
+        unsafe { SortedVec::uncheck_from_vec(Vec::new(), rule_is_less) }
+    }
+
+    /// Binary insertion, `value` goes after the elements equal to it.
+    ///
+    /// Like `insert_by_ord` in `c-src/insertion-sort.c`,
+    /// but searching the place instead of sliding from the end.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sort::SortedVec;
+    /// let mut vec = SortedVec::new(|a: &(i32, char), b: &(i32, char)| a.0 < b.0);
+    /// vec.insert((2, 'a'));
+    /// vec.insert((1, 'a'));
+    /// vec.insert((2, 'b'));
+    /// assert_eq!(*vec, [(1, 'a'), (2, 'a'), (2, 'b')]);
+    /// ```
+    pub fn insert(&mut self, value: T) -> usize {
+        let index = self.upper_bound(&value);
+        self.vec.insert(index, value);
+        index
+    }
+
+    pub fn remove(&mut self, index: usize) -> T {
+        self.vec.remove(index)
+    }
+
+    /// Remove the first element equal to `value`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sort::SortedVec;
+    /// let mut vec = SortedVec::try_from_vec(vec![1, 2, 2, 3], i32::lt).unwrap();
+    /// assert_eq!(vec.remove_item(&2), Some(2));
+    /// assert_eq!(vec.remove_item(&4), None);
+    /// assert_eq!(*vec, [1, 2, 3]);
+    /// ```
+    pub fn remove_item(&mut self, value: &T) -> Option<T> {
+        if self.contains(value) {
+            Some(self.vec.remove(self.lower_bound(value)))
+        } else {
+            None
+        }
+    }
+
+    pub fn retain(&mut self, f: impl FnMut(&T) -> bool) {
+        self.vec.retain(f);
+    }
+
+    /// Remove the elements whose values fall into `range`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sort::SortedVec;
+    /// let mut vec = SortedVec::try_from_vec(vec![1, 2, 3, 4, 5], i32::lt).unwrap();
+    /// let drained: Vec<_> = vec.drain_range(2..4).collect();
+    /// assert_eq!(drained, [2, 3]);
+    /// assert_eq!(*vec, [1, 4, 5]);
+    /// ```
+    pub fn drain_range<R: RangeBounds<T>>(&mut self, range: R) -> std::vec::Drain<'_, T> {
+        let range = self.index_range(range);
+        self.vec.drain(range)
+    }
+}
+
+/// Sort the new batch, then merge it after the equal elements already in.
+///
+/// # Examples
+///
+/// ```
+/// use sort::SortedVec;
+/// let mut vec = SortedVec::try_from_vec(vec![1, 5, 9], i32::lt).unwrap();
+/// vec.extend([8, 2, 5]);
+/// assert_eq!(*vec, [1, 2, 5, 5, 8, 9]);
+/// ```
+impl<T, F: FnMut(&T, &T) -> bool> Extend<T> for SortedVec<T, F> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let is_less = self.rule_is_less.get_mut();
+
+        let mut batch: Vec<T> = iter.into_iter().collect();
+        batch.sort_by(|a, b| {
+            if is_less(a, b) {
+                Ordering::Less
+            } else {
+                Ordering::Greater
+            }
+        });
+
+        let vec = std::mem::take(&mut self.vec);
+        self.vec = merge_iter_by(vec, batch, is_less).collect();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::read_bench_data;
+
+    #[test]
+    fn empty() {
+        let mut vec = SortedVec::new(i32::lt);
+        assert!(vec.is_empty());
+        assert_eq!(vec.remove_item(&0), None);
+        assert_eq!(vec.drain_range(..).count(), 0);
+        vec.extend([]);
+        assert!(vec.is_empty());
+    }
+
+    #[test]
+    fn insert() {
+        let mut vec = SortedVec::new(i32::lt);
+        assert_eq!(vec.insert(3), 0);
+        assert_eq!(vec.insert(1), 0);
+        assert_eq!(vec.insert(3), 2);
+        assert_eq!(vec.insert(2), 1);
+        assert_eq!(*vec, [1, 2, 3, 3]);
+    }
+
+    #[test]
+    fn insert_stable() {
+        let mut vec = SortedVec::new(|a: &(i32, usize), b: &(i32, usize)| a.0 < b.0);
+        [3, 1, 3, 1, 2]
+            .into_iter()
+            .zip(0..)
+            .for_each(|r| _ = vec.insert(r));
+        assert_eq!(*vec, [(1, 1), (1, 3), (2, 4), (3, 0), (3, 2)]);
+    }
+
+    #[test]
+    fn remove() {
+        let mut vec = SortedVec::try_from_vec(vec![1, 2, 3], i32::lt).unwrap();
+        assert_eq!(vec.remove(1), 2);
+        assert_eq!(*vec, [1, 3]);
+    }
+
+    #[test]
+    fn retain() {
+        let mut vec = SortedVec::try_from_vec(vec![1, 2, 3, 4], i32::lt).unwrap();
+        vec.retain(|x| x % 2 == 0);
+        assert_eq!(*vec, [2, 4]);
+        assert!(vec.force_check());
+    }
+
+    #[test]
+    fn drain_range() {
+        let mut vec = SortedVec::try_from_vec(vec![1, 2, 2, 3, 5], i32::lt).unwrap();
+        assert_eq!(vec.drain_range(2..=2).collect::<Vec<_>>(), [2, 2]);
+        assert_eq!(vec.drain_range(4..).collect::<Vec<_>>(), [5]);
+        assert_eq!(*vec, [1, 3]);
+    }
+
+    #[test]
+    fn extend_stable() {
+        let mut vec = SortedVec::new(|a: &(i32, char), b: &(i32, char)| a.0 < b.0);
+        vec.extend([(2, 'a'), (1, 'a')]);
+        vec.extend([(2, 'b'), (1, 'b'), (2, 'c')]);
+        assert_eq!(*vec, [(1, 'a'), (1, 'b'), (2, 'a'), (2, 'b'), (2, 'c')]);
+    }
+
+    #[test]
+    fn not_copy() {
+        let mut vec = SortedVec::new(|a: &String, b: &String| a.len() < b.len());
+        vec.extend(["ccc", "a"].map(String::from));
+        vec.insert(String::from("bb"));
+        assert_eq!(vec.remove_item(&String::from("xx")).unwrap(), "bb");
+        assert_eq!(*vec, ["a", "ccc"]);
+    }
+
+    #[test]
+    fn bench_data() {
+        let random: Vec<i32> = read_bench_data!("random");
+        let stroll: Vec<i32> = read_bench_data!("stroll");
+
+        let mut vec = SortedVec::new(i32::lt);
+        random[..100].iter().for_each(|x| _ = vec.insert(*x));
+        vec.extend(random[100..].iter().copied());
+        vec.extend(stroll.iter().copied());
+        assert_eq!(vec.verify(), Ok(()));
+        assert_eq!(vec.len(), random.len() + stroll.len());
+
+        stroll
+            .iter()
+            .for_each(|x| assert!(vec.remove_item(x).is_some()));
+        let mut expected = random.clone();
+        expected.sort();
+        assert_eq!(*vec, expected);
+    }
+}
@@ -1,10 +1,12 @@
-use crate::{Solution, Sorted};
+use crate::{SliceSolution, Solution, Sorted, SortedSlice, StableSolution};
 use std::cmp::Ordering;
 
 #[derive(Copy, Clone)]
 pub struct Stable;
 
-impl<T: Ord, const N: usize> Solution<T, N> for Stable {
+impl StableSolution for Stable {}
+
+impl<T, const N: usize> Solution<T, N> for Stable {
     fn sort_method<F>(array: [T; N], is_less: F) -> Sorted<T, N, impl FnMut(&T, &T) -> bool>
     where
         F: FnMut(&T, &T) -> bool,
@@ -24,6 +26,28 @@ impl<T: Ord, const N: usize> Solution<T, N> for Stable {
     }
 }
 
+impl<T> SliceSolution<T> for Stable {
+    fn sort_slice_method<F>(
+        slice: &mut [T],
+        is_less: F,
+    ) -> SortedSlice<'_, T, impl FnMut(&T, &T) -> bool>
+    where
+        F: FnMut(&T, &T) -> bool,
+    {
+        let mut is_less = is_less;
+
+        slice.sort_by(|a, b| {
+            if is_less(a, b) {
+                Ordering::Less
+            } else {
+                Ordering::Greater
+            }
+        });
+
+        unsafe { SortedSlice::uncheck_from_slice(slice, is_less) }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -71,4 +95,189 @@ mod tests {
     fn high_sample_sin_with_noise() {
         test_high_sample_sin_with_noise(Stable);
     }
+
+    mod cached_key {
+        use super::*;
+        use std::cell::Cell;
+
+        #[test]
+        fn calls_key_once() {
+            let data: [i32; 1000] = crate::read_bench_data!("gaussian-with-noise");
+            let calls = Cell::new(0);
+            let sorted = Stable::sort_by_cached_key(data, |x| {
+                calls.set(calls.get() + 1);
+                x.to_string()
+            });
+            assert_eq!(calls.get(), 1000);
+
+            let mut expected = data;
+            expected.sort_by_key(|x| x.to_string());
+            assert_eq!(*sorted, expected);
+        }
+
+        #[test]
+        fn slice_calls_key_once() {
+            for (name, data) in bench_data() {
+                let calls = Cell::new(0);
+                let mut vec = data.clone();
+                let sorted = Stable::sort_slice_by_cached_key(&mut vec, |x| {
+                    calls.set(calls.get() + 1);
+                    x.div_euclid(16)
+                });
+                assert_eq!(calls.get(), data.len(), "{}", name);
+
+                let mut expected = data.clone();
+                expected.sort_by_key(|x| x.div_euclid(16));
+                assert_eq!(*sorted, expected, "{}", name);
+            }
+        }
+
+        #[test]
+        fn not_copy() {
+            let mut vec: Vec<String> = ["b", "C", "a", "B"].map(String::from).to_vec();
+            let sorted = Stable::sort_slice_by_cached_key(&mut vec, |s| s.to_lowercase());
+            assert!(sorted.force_check());
+            assert_eq!(*sorted, ["a", "b", "B", "C"]);
+        }
+    }
+
+    mod stability {
+        use super::*;
+
+        #[test]
+        fn key_then() {
+            let data: [i32; 1000] = crate::read_bench_data!("gaussian-with-noise");
+            let records = tag(data).map(|(x, i)| (x, i % 7));
+            let sorted = Stable::sort_by_key_then(records, |r| r.0, |r| r.1);
+            assert!(sorted.force_check());
+            let mut expected = records;
+            expected.sort();
+            assert_eq!(sorted.take(), expected);
+        }
+
+        #[test]
+        fn random() {
+            test_stable_random(Stable);
+        }
+
+        #[test]
+        fn stroll() {
+            test_stable_stroll(Stable);
+        }
+
+        #[test]
+        fn trend_increasing() {
+            test_stable_trend_increasing(Stable);
+        }
+
+        #[test]
+        fn gaussian_with_noise() {
+            test_stable_gaussian_with_noise(Stable);
+        }
+
+        #[test]
+        fn low_sample_sin_with_noise() {
+            test_stable_low_sample_sin_with_noise(Stable);
+        }
+
+        #[test]
+        fn high_sample_sin_with_noise() {
+            test_stable_high_sample_sin_with_noise(Stable);
+        }
+
+        #[test]
+        fn detect_unstable() {
+            let mut records = tag([1, 0, 1, 0]);
+            records.sort_unstable_by_key(|r| (r.0, std::cmp::Reverse(r.1)));
+            assert!(!is_stable(&records, |r| r.0));
+        }
+    }
+
+    mod slice {
+        use super::*;
+
+        #[test]
+        fn simple() {
+            let mut vec = vec![9, 3, 5, 1, 7, 4, 6, 2, 8, 0];
+            let a = Stable::sort_slice_method(&mut vec, |a, b| a < b);
+            assert!(a.force_check());
+            assert_eq!(a.take(), &[0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+        }
+
+        #[test]
+        fn empty() {
+            test_slice_empty(Stable);
+        }
+
+        #[test]
+        fn random() {
+            test_slice_random(Stable);
+        }
+
+        #[test]
+        fn stroll() {
+            test_slice_stroll(Stable);
+        }
+
+        #[test]
+        fn trend_increasing() {
+            test_slice_trend_increasing(Stable);
+        }
+
+        #[test]
+        fn gaussian_with_noise() {
+            test_slice_gaussian_with_noise(Stable);
+        }
+
+        #[test]
+        fn low_sample_sin_with_noise() {
+            test_slice_low_sample_sin_with_noise(Stable);
+        }
+
+        #[test]
+        fn high_sample_sin_with_noise() {
+            test_slice_high_sample_sin_with_noise(Stable);
+        }
+
+        mod stability {
+            use super::*;
+
+            #[test]
+            fn key_then() {
+                let mut vec = vec![(2, 'b'), (1, 'b'), (2, 'a'), (1, 'a')];
+                let sorted = Stable::sort_slice_by_key_then(&mut vec, |x| x.0, |x| x.1);
+                assert_eq!(*sorted, [(1, 'a'), (1, 'b'), (2, 'a'), (2, 'b')]);
+            }
+
+            #[test]
+            fn random() {
+                test_slice_stable_random(Stable);
+            }
+
+            #[test]
+            fn stroll() {
+                test_slice_stable_stroll(Stable);
+            }
+
+            #[test]
+            fn trend_increasing() {
+                test_slice_stable_trend_increasing(Stable);
+            }
+
+            #[test]
+            fn gaussian_with_noise() {
+                test_slice_stable_gaussian_with_noise(Stable);
+            }
+
+            #[test]
+            fn low_sample_sin_with_noise() {
+                test_slice_stable_low_sample_sin_with_noise(Stable);
+            }
+
+            #[test]
+            fn high_sample_sin_with_noise() {
+                test_slice_stable_high_sample_sin_with_noise(Stable);
+            }
+        }
+    }
 }
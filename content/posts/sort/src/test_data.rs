@@ -3,6 +3,16 @@ use rand::{thread_rng, Rng};
 use std::mem::MaybeUninit;
 use std::ops::AddAssign;
 
+/// Names of the files in `bench-data`, generated by `gen-bench-data.sh`.
+pub const BENCH_DATA: [&str; 6] = [
+    "random",
+    "stroll",
+    "trend-increasing",
+    "gaussian-with-noise",
+    "low-sample-sin-with-noise",
+    "high-sample-sin-with-noise",
+];
+
 /// Generates an array of random values in the given range.
 ///
 /// # Examples
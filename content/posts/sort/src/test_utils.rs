@@ -10,7 +10,8 @@ macro_rules! read_bench_data {
     };
 }
 
-use crate::Solution;
+use crate::test_data::BENCH_DATA;
+use crate::{SliceSolution, Solution};
 use std::cmp::Ordering;
 
 pub fn test_sort<T, const N: usize, S>(_: S, array: [T; N]) -> bool
@@ -105,3 +106,382 @@ pub fn test_high_sample_sin_with_noise(solution: impl Solution<i32, 1000>) {
     assert!(test_sort_by(solution, data, |a, b| a.cmp(b)));
     assert!(test_sort_by_key(solution, data, |i| *i));
 }
+
+pub fn test_slice_sort<T, S>(_: S, slice: &[T]) -> bool
+where
+    T: Ord + Clone,
+    S: SliceSolution<T>,
+{
+    let mut vec = slice.to_vec();
+    let sorted = S::sort_slice(&mut vec).take().to_vec();
+    vec.sort();
+    sorted == vec
+}
+
+pub fn test_slice_sort_by<T, S, F>(_: S, slice: &[T], cmp: F) -> bool
+where
+    T: Ord + Clone,
+    S: SliceSolution<T>,
+    F: Fn(&T, &T) -> Ordering,
+{
+    let mut vec = slice.to_vec();
+    let sorted = S::sort_slice_by(&mut vec, |a, b| cmp(a, b)).take().to_vec();
+    vec.sort_by(|a, b| cmp(a, b));
+    sorted == vec
+}
+
+pub fn test_slice_sort_by_key<T, S, F, K>(_: S, slice: &[T], f: F) -> bool
+where
+    T: Eq + Clone,
+    S: SliceSolution<T>,
+    F: FnMut(&T) -> K,
+    K: Ord,
+{
+    let mut f = f;
+    let mut vec = slice.to_vec();
+    let sorted = S::sort_slice_by_key(&mut vec, |a| f(a)).take().to_vec();
+    vec.sort_by_key(|a| f(a));
+    sorted == vec
+}
+
+pub fn test_slice(solution: impl SliceSolution<i32>, data: &[i32]) {
+    assert!(test_slice_sort(solution, data));
+    assert!(test_slice_sort_by(solution, data, |a, b| a.cmp(b)));
+    assert!(test_slice_sort_by_key(solution, data, |i| *i));
+}
+
+pub fn test_slice_empty(solution: impl SliceSolution<i32>) {
+    test_slice(solution, &[]);
+}
+
+pub fn test_slice_random(solution: impl SliceSolution<i32>) {
+    let data: Vec<i32> = read_bench_data!("random");
+    test_slice(solution, &data);
+}
+
+pub fn test_slice_stroll(solution: impl SliceSolution<i32>) {
+    let data: Vec<i32> = read_bench_data!("stroll");
+    test_slice(solution, &data);
+}
+
+pub fn test_slice_trend_increasing(solution: impl SliceSolution<i32>) {
+    let data: Vec<i32> = read_bench_data!("trend-increasing");
+    test_slice(solution, &data);
+}
+
+pub fn test_slice_gaussian_with_noise(solution: impl SliceSolution<i32>) {
+    let data: Vec<i32> = read_bench_data!("gaussian-with-noise");
+    test_slice(solution, &data);
+}
+
+pub fn test_slice_low_sample_sin_with_noise(solution: impl SliceSolution<i32>) {
+    let data: Vec<i32> = read_bench_data!("low-sample-sin-with-noise");
+    test_slice(solution, &data);
+}
+
+pub fn test_slice_high_sample_sin_with_noise(solution: impl SliceSolution<i32>) {
+    let data: Vec<i32> = read_bench_data!("high-sample-sin-with-noise");
+    test_slice(solution, &data);
+}
+
+// The `csort` functions can not carry a tag, so they sort the index packed with
+// the key into a `u64` ordered only by its high half, see `test_packed_stable`.
+
+/// Tag every element with its original index.
+pub fn tag<const N: usize>(array: [i32; N]) -> [(i32, usize); N] {
+    let mut i = 0;
+    array.map(|x| {
+        i += 1;
+        (x, i - 1)
+    })
+}
+
+/// Equal keys keep the order of their original index.
+pub fn is_stable<K: Ord>(
+    records: &[(i32, usize)],
+    mut key: impl FnMut(&(i32, usize)) -> K,
+) -> bool {
+    records
+        .windows(2)
+        .all(|w| key(&w[0]) < key(&w[1]) || (key(&w[0]) == key(&w[1]) && w[0].1 < w[1].1))
+}
+
+pub fn test_stable_sort_by_key<const N: usize, S, F, K>(_: S, array: [i32; N], key: F) -> bool
+where
+    S: Solution<(i32, usize), N>,
+    F: Fn(&(i32, usize)) -> K,
+    K: Ord,
+{
+    let sorted = S::sort_by_key(tag(array), |r| key(r)).take();
+    is_stable(&sorted, key)
+}
+
+pub fn test_slice_stable_sort_by_key<S, F, K>(_: S, slice: &[i32], key: F) -> bool
+where
+    S: SliceSolution<(i32, usize)>,
+    F: Fn(&(i32, usize)) -> K,
+    K: Ord,
+{
+    let mut records: Vec<_> = slice.iter().copied().zip(0..).collect();
+    let sorted = S::sort_slice_by_key(&mut records, |r| key(r)).take();
+    is_stable(sorted, key)
+}
+
+/// Sort by the value and by a coarse bucket of it, so that equal keys are common.
+pub fn test_stable<const N: usize>(solution: impl Solution<(i32, usize), N>, data: [i32; N]) {
+    assert!(test_stable_sort_by_key(solution, data, |r| r.0));
+    assert!(test_stable_sort_by_key(solution, data, |r| r
+        .0
+        .div_euclid(16)));
+    assert!(test_stable_sort_by_key(solution, data, |r| r.0 % 2 == 0));
+}
+
+pub fn test_slice_stable(solution: impl SliceSolution<(i32, usize)>, data: &[i32]) {
+    assert!(test_slice_stable_sort_by_key(solution, data, |r| r.0));
+    assert!(test_slice_stable_sort_by_key(solution, data, |r| r
+        .0
+        .div_euclid(16)));
+    assert!(test_slice_stable_sort_by_key(solution, data, |r| r.0 % 2 == 0));
+}
+
+pub fn test_stable_random(solution: impl Solution<(i32, usize), 10_000>) {
+    test_stable(solution, read_bench_data!("random"));
+}
+
+pub fn test_stable_stroll(solution: impl Solution<(i32, usize), 10_000>) {
+    test_stable(solution, read_bench_data!("stroll"));
+}
+
+pub fn test_stable_trend_increasing(solution: impl Solution<(i32, usize), 1000>) {
+    test_stable(solution, read_bench_data!("trend-increasing"));
+}
+
+pub fn test_stable_gaussian_with_noise(solution: impl Solution<(i32, usize), 1000>) {
+    test_stable(solution, read_bench_data!("gaussian-with-noise"));
+}
+
+pub fn test_stable_low_sample_sin_with_noise(solution: impl Solution<(i32, usize), 1000>) {
+    test_stable(solution, read_bench_data!("low-sample-sin-with-noise"));
+}
+
+pub fn test_stable_high_sample_sin_with_noise(solution: impl Solution<(i32, usize), 1000>) {
+    test_stable(solution, read_bench_data!("high-sample-sin-with-noise"));
+}
+
+pub fn test_slice_stable_random(solution: impl SliceSolution<(i32, usize)>) {
+    let data: Vec<i32> = read_bench_data!("random");
+    test_slice_stable(solution, &data);
+}
+
+pub fn test_slice_stable_stroll(solution: impl SliceSolution<(i32, usize)>) {
+    let data: Vec<i32> = read_bench_data!("stroll");
+    test_slice_stable(solution, &data);
+}
+
+pub fn test_slice_stable_trend_increasing(solution: impl SliceSolution<(i32, usize)>) {
+    let data: Vec<i32> = read_bench_data!("trend-increasing");
+    test_slice_stable(solution, &data);
+}
+
+pub fn test_slice_stable_gaussian_with_noise(solution: impl SliceSolution<(i32, usize)>) {
+    let data: Vec<i32> = read_bench_data!("gaussian-with-noise");
+    test_slice_stable(solution, &data);
+}
+
+pub fn test_slice_stable_low_sample_sin_with_noise(solution: impl SliceSolution<(i32, usize)>) {
+    let data: Vec<i32> = read_bench_data!("low-sample-sin-with-noise");
+    test_slice_stable(solution, &data);
+}
+
+pub fn test_slice_stable_high_sample_sin_with_noise(solution: impl SliceSolution<(i32, usize)>) {
+    let data: Vec<i32> = read_bench_data!("high-sample-sin-with-noise");
+    test_slice_stable(solution, &data);
+}
+
+/// Pack every element with its original index, the key in the high half.
+pub fn pack(data: &[i32], key: impl Fn(i32) -> i32) -> Vec<u64> {
+    data.iter()
+        .zip(0..)
+        .map(|(&x, i)| ((key(x) as u32 ^ 1 << 31) as u64) << 32 | i)
+        .collect()
+}
+
+/// Packed records are sorted by key, and equal keys by their original index.
+pub fn is_packed_stable(packed: &[u64]) -> bool {
+    packed.windows(2).all(|w| {
+        let (a, b) = (w[0] >> 32, w[1] >> 32);
+        a < b || (a == b && (w[0] as u32) < (w[1] as u32))
+    })
+}
+
+/// Sort packed by the value, a coarse bucket of it and its parity,
+/// as [`test_stable`] does with tags.
+pub fn test_packed_stable(mut solution: impl FnMut(&mut [u64]), data: &[i32]) {
+    let keys: [fn(i32) -> i32; 3] = [|x| x, |x| x.div_euclid(16), |x| (x % 2 == 0) as i32];
+    for key in keys {
+        let mut packed = pack(data, key);
+        solution(&mut packed);
+        assert!(is_packed_stable(&packed));
+
+        let mut index: Vec<u32> = packed.iter().map(|&r| r as u32).collect();
+        index.sort();
+        assert!(index.into_iter().eq(0..data.len() as u32));
+    }
+}
+
+/// All files in `bench-data` with their names, see [`BENCH_DATA`].
+pub fn bench_data() -> [(&'static str, Vec<i32>); BENCH_DATA.len()] {
+    BENCH_DATA.map(|name| {
+        let path = format!("{}/bench-data/{}.json", env!("CARGO_MANIFEST_DIR"), name);
+        let json = std::fs::read_to_string(path).unwrap();
+        (name, serde_json::from_str(&json).unwrap())
+    })
+}
+
+/// Benchmarks on the files in `bench-data`.
+pub mod bench {
+    extern crate test;
+    pub use test::Bencher;
+
+    /// What every iteration sorts.
+    #[derive(Clone, Copy)]
+    pub enum Input {
+        /// The same array again, already sorted after the first iteration,
+        /// as the C benches of the article were measured.
+        Reused,
+        /// A fresh copy of the data.
+        Fresh,
+    }
+
+    fn iter<const N: usize>(
+        b: &mut Bencher,
+        data: [i32; N],
+        solution: fn(&mut [i32]),
+        input: Input,
+    ) {
+        let mut data = data;
+        match input {
+            Input::Reused => b.iter(|| solution(&mut data)),
+            Input::Fresh => b.iter(|| solution(&mut data.clone())),
+        }
+    }
+
+    pub fn random(b: &mut Bencher, solution: fn(&mut [i32]), input: Input) {
+        iter::<10000>(b, crate::read_bench_data!("random"), solution, input);
+    }
+
+    pub fn stroll(b: &mut Bencher, solution: fn(&mut [i32]), input: Input) {
+        iter::<10000>(b, crate::read_bench_data!("stroll"), solution, input);
+    }
+
+    pub fn trend_increasing(b: &mut Bencher, solution: fn(&mut [i32]), input: Input) {
+        iter::<1000>(
+            b,
+            crate::read_bench_data!("trend-increasing"),
+            solution,
+            input,
+        );
+    }
+
+    pub fn gaussian_with_noise(b: &mut Bencher, solution: fn(&mut [i32]), input: Input) {
+        iter::<1000>(
+            b,
+            crate::read_bench_data!("gaussian-with-noise"),
+            solution,
+            input,
+        );
+    }
+
+    pub fn low_sample_sin_with_noise(b: &mut Bencher, solution: fn(&mut [i32]), input: Input) {
+        iter::<1000>(
+            b,
+            crate::read_bench_data!("low-sample-sin-with-noise"),
+            solution,
+            input,
+        );
+    }
+
+    pub fn high_sample_sin_with_noise(b: &mut Bencher, solution: fn(&mut [i32]), input: Input) {
+        iter::<1000>(
+            b,
+            crate::read_bench_data!("high-sample-sin-with-noise"),
+            solution,
+            input,
+        );
+    }
+}
+
+/// Define a `#[bench]` of `solution: fn(&mut [i32])` for every [`bench`] data,
+/// sorting the [`bench::Input`] given, `Reused` by default.
+#[macro_export]
+macro_rules! bench_all {
+    ($solution:expr) => {
+        $crate::bench_all!($solution, Reused);
+    };
+    ($solution:expr, $input:ident) => {
+        #[bench]
+        fn random(b: &mut $crate::test_utils::bench::Bencher) {
+            use $crate::test_utils::bench::{self, Input};
+            bench::random(b, $solution, Input::$input);
+        }
+        #[bench]
+        fn stroll(b: &mut $crate::test_utils::bench::Bencher) {
+            use $crate::test_utils::bench::{self, Input};
+            bench::stroll(b, $solution, Input::$input);
+        }
+        #[bench]
+        fn trend_increasing(b: &mut $crate::test_utils::bench::Bencher) {
+            use $crate::test_utils::bench::{self, Input};
+            bench::trend_increasing(b, $solution, Input::$input);
+        }
+        #[bench]
+        fn gaussian_with_noise(b: &mut $crate::test_utils::bench::Bencher) {
+            use $crate::test_utils::bench::{self, Input};
+            bench::gaussian_with_noise(b, $solution, Input::$input);
+        }
+        #[bench]
+        fn low_sample_sin_with_noise(b: &mut $crate::test_utils::bench::Bencher) {
+            use $crate::test_utils::bench::{self, Input};
+            bench::low_sample_sin_with_noise(b, $solution, Input::$input);
+        }
+        #[bench]
+        fn high_sample_sin_with_noise(b: &mut $crate::test_utils::bench::Bencher) {
+            use $crate::test_utils::bench::{self, Input};
+            bench::high_sample_sin_with_noise(b, $solution, Input::$input);
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Sorted;
+
+    /// Not a [`crate::StableSolution`], the harness must tell.
+    #[derive(Copy, Clone)]
+    struct Unstable;
+
+    impl<T, const N: usize> Solution<T, N> for Unstable {
+        fn sort_method<F>(array: [T; N], is_less: F) -> Sorted<T, N, impl FnMut(&T, &T) -> bool>
+        where
+            F: FnMut(&T, &T) -> bool,
+        {
+            let mut is_less = is_less;
+            let mut array = array;
+            array.sort_unstable_by(|a, b| {
+                if is_less(a, b) {
+                    Ordering::Less
+                } else {
+                    Ordering::Greater
+                }
+            });
+            unsafe { Sorted::uncheck_from_array(array, is_less) }
+        }
+    }
+
+    #[test]
+    fn unstable() {
+        let data: [i32; 10_000] = read_bench_data!("random");
+        assert!(!test_stable_sort_by_key(Unstable, data, |r| r.0 % 2 == 0));
+    }
+}
@@ -0,0 +1,336 @@
+//! Record every step of a sort, so that a front-end can replay it.
+//!
+//! The algorithms here follow the C implementations in `c-src`
+//! step by step, but report each comparison, swap and write with indices.
+//! They are meant for small arrays.
+//!
+//! # Examples
+//!
+//! ```
+//! use sort::trace::{bubble_sort, Event};
+//! let trace = bubble_sort(&[2, 1]);
+//! assert_eq!(trace.events, [Event::Compare { i: 0, j: 1 }, Event::Swap { i: 0, j: 1 }]);
+//! assert_eq!(trace.replay(), [1, 2]);
+//! assert_eq!(
+//!     trace.to_json(),
+//!     r#"{"algorithm":"bubble","input":[2,1],"events":[{"type":"compare","i":0,"j":1},{"type":"swap","i":0,"j":1}]}"#
+//! );
+//! ```
+
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Event {
+    /// `array[i]` is compared with `array[j]`.
+    Compare { i: usize, j: usize },
+    /// `array[i]` and `array[j]` are swapped.
+    Swap { i: usize, j: usize },
+    /// `value` is written to `array[index]`.
+    Write { index: usize, value: i32 },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Trace {
+    pub algorithm: &'static str,
+    pub input: Vec<i32>,
+    pub events: Vec<Event>,
+}
+
+impl Trace {
+    /// Apply all events to `input`, giving the sorted array.
+    pub fn replay(&self) -> Vec<i32> {
+        let mut array = self.input.clone();
+        for event in &self.events {
+            match *event {
+                Event::Compare { .. } => {}
+                Event::Swap { i, j } => array.swap(i, j),
+                Event::Write { index, value } => array[index] = value,
+            }
+        }
+        array
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+}
+
+struct Tracer {
+    array: Vec<i32>,
+    events: Vec<Event>,
+}
+
+impl Tracer {
+    fn new(input: &[i32]) -> Self {
+        Tracer {
+            array: input.to_vec(),
+            events: Vec::new(),
+        }
+    }
+
+    /// `array[i] <= array[j]`
+    fn less_equal(&mut self, i: usize, j: usize) -> bool {
+        self.events.push(Event::Compare { i, j });
+        self.array[i] <= self.array[j]
+    }
+
+    /// `array[i] > array[j]`
+    fn greater(&mut self, i: usize, j: usize) -> bool {
+        !self.less_equal(i, j)
+    }
+
+    fn swap(&mut self, i: usize, j: usize) {
+        self.events.push(Event::Swap { i, j });
+        self.array.swap(i, j);
+    }
+
+    fn write(&mut self, index: usize, value: i32) {
+        self.events.push(Event::Write { index, value });
+        self.array[index] = value;
+    }
+
+    fn finish(self, algorithm: &'static str, input: &[i32]) -> Trace {
+        debug_assert!(self.array.is_sorted());
+        Trace {
+            algorithm,
+            input: input.to_vec(),
+            events: self.events,
+        }
+    }
+}
+
+/// See `c-src/bubble-sort.c`.
+pub fn bubble_sort(input: &[i32]) -> Trace {
+    let mut t = Tracer::new(input);
+    for len in (2..=input.len()).rev() {
+        for i in 1..len {
+            if t.greater(i - 1, i) {
+                t.swap(i - 1, i);
+            }
+        }
+    }
+    t.finish("bubble", input)
+}
+
+/// See `c-src/insertion-sort.c`,
+/// the element is moved down by swapping instead of being held aside.
+pub fn insertion_sort(input: &[i32]) -> Trace {
+    let mut t = Tracer::new(input);
+    for i in 1..input.len() {
+        for j in (1..=i).rev() {
+            if !t.greater(j - 1, j) {
+                break;
+            }
+            t.swap(j - 1, j);
+        }
+    }
+    t.finish("insertion", input)
+}
+
+/// See `merge_two_sorted_array` in `c-src/merge-two-sorted-array.c`.
+fn merge_two_sorted(t: &mut Tracer, start: usize, len: usize, split: usize) {
+    if len <= 1 || split >= len || split == 0 {
+        return;
+    }
+
+    let mut stack = Vec::with_capacity(len);
+    let (mut left, divide) = (start, start + split);
+    let (mut right, end) = (divide, start + len);
+
+    loop {
+        if t.less_equal(left, right) {
+            stack.push(t.array[left]);
+            left += 1;
+            if left >= divide {
+                break;
+            }
+        } else {
+            stack.push(t.array[right]);
+            right += 1;
+            if right >= end {
+                // move the remaining left elements to the back
+                let lave = divide - left;
+                for k in (0..lave).rev() {
+                    t.write(end - lave + k, t.array[left + k]);
+                }
+                break;
+            }
+        }
+    }
+
+    for (k, value) in stack.into_iter().enumerate() {
+        t.write(start + k, value);
+    }
+}
+
+fn merge_sort_rec(t: &mut Tracer, start: usize, len: usize) {
+    if len <= 1 {
+        return;
+    }
+
+    let half = len / 2;
+    merge_sort_rec(t, start, half);
+    merge_sort_rec(t, start + half, len - half);
+    merge_two_sorted(t, start, len, half);
+}
+
+/// See `c-src/merge-sort.c`.
+pub fn merge_sort(input: &[i32]) -> Trace {
+    let mut t = Tracer::new(input);
+    merge_sort_rec(&mut t, 0, input.len());
+    t.finish("merge", input)
+}
+
+fn quick_sort_rec(t: &mut Tracer, start: usize, len: usize) {
+    if len <= 1 {
+        return;
+    }
+
+    // take first as pivot
+    let pivot = start;
+    let mut left = start + 1;
+    let mut right = start + len;
+
+    while left < right {
+        if t.less_equal(left, pivot) {
+            left += 1;
+        } else {
+            right -= 1;
+            t.swap(left, right);
+        }
+    }
+
+    t.swap(left - 1, pivot);
+
+    quick_sort_rec(t, start, left - 1 - start);
+    quick_sort_rec(t, right, start + len - right);
+}
+
+/// See `c-src/quick-sort.c`.
+pub fn quick_sort(input: &[i32]) -> Trace {
+    let mut t = Tracer::new(input);
+    quick_sort_rec(&mut t, 0, input.len());
+    t.finish("quick", input)
+}
+
+/// See `c-src/radix-sort.c`, base 256 with 4 keys.
+///
+/// Every pass distributes the elements into buckets by one byte
+/// and writes them back bucket by bucket.
+pub fn radix_lsd_sort(input: &[i32]) -> Trace {
+    const BASE: u32 = 256;
+    // preserve the order of signed numbers as unsigned
+    let key = |x: i32| (x as u32) ^ (1 << 31);
+
+    let mut t = Tracer::new(input);
+    let mut offset = 1;
+    for _ in 0..4 {
+        let mut bucket = vec![Vec::new(); BASE as usize];
+        for &x in &t.array {
+            bucket[(key(x) / offset % BASE) as usize].push(x);
+        }
+        for (index, value) in bucket.into_iter().flatten().enumerate() {
+            t.write(index, value);
+        }
+        offset = offset.wrapping_mul(BASE);
+    }
+    t.finish("radix_lsd", input)
+}
+
+pub type TraceFn = fn(&[i32]) -> Trace;
+
+/// Every traceable algorithm with its name in [`crate::registry`].
+pub const TRACEABLE: [(&str, TraceFn); 5] = [
+    ("bubble", bubble_sort),
+    ("insertion", insertion_sort),
+    ("merge", merge_sort),
+    ("quick", quick_sort),
+    ("radix_lsd", radix_lsd_sort),
+];
+
+/// Trace an algorithm by its name in [`crate::registry`].
+pub fn trace(name: &str, input: &[i32]) -> Option<Trace> {
+    TRACEABLE
+        .iter()
+        .find(|(traceable, _)| *traceable == name)
+        .map(|(_, trace)| trace(input))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::bench_data;
+
+    fn test_replay(input: &[i32]) {
+        let mut expected = input.to_vec();
+        expected.sort();
+        for (name, _) in TRACEABLE {
+            let trace = trace(name, input).unwrap();
+            assert_eq!(trace.algorithm, name);
+            assert_eq!(trace.replay(), expected, "{} on {:?}", name, input);
+        }
+    }
+
+    #[test]
+    fn small() {
+        test_replay(&[]);
+        test_replay(&[1]);
+        test_replay(&[2, 1]);
+        test_replay(&[3, 1, 2]);
+        test_replay(&[2, 3, 2, 1]);
+        test_replay(&[5, 1, 4, 2, 3]);
+        test_replay(&[-1, 8, 19, -3, 996, i32::MIN, i32::MAX, 0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn bench_data_replay() {
+        for (_, data) in bench_data() {
+            test_replay(&data[..200]);
+        }
+    }
+
+    #[test]
+    fn indices_in_bounds() {
+        let input = [9, 3, 5, 1, 7, 4, 6, 2, 8, 0];
+        for (name, _) in TRACEABLE {
+            let trace = trace(name, &input).unwrap();
+            assert!(trace.events.iter().all(|event| match *event {
+                Event::Compare { i, j } | Event::Swap { i, j } => i < 10 && j < 10,
+                Event::Write { index, .. } => index < 10,
+            }));
+        }
+    }
+
+    #[test]
+    fn events() {
+        let trace = quick_sort(&[1, 2]);
+        assert_eq!(
+            trace.events,
+            [
+                Event::Compare { i: 1, j: 0 },
+                Event::Swap { i: 1, j: 1 },
+                Event::Swap { i: 0, j: 0 }
+            ]
+        );
+
+        let trace = merge_sort(&[2, 1]);
+        assert_eq!(
+            trace.events,
+            [
+                Event::Compare { i: 0, j: 1 },
+                Event::Write { index: 1, value: 2 },
+                Event::Write { index: 0, value: 1 }
+            ]
+        );
+
+        assert!(super::trace("bogo", &[]).is_none());
+    }
+
+    #[test]
+    fn registered() {
+        for (name, _) in TRACEABLE {
+            assert!(crate::registry::find(name).is_some(), "{}", name);
+        }
+    }
+}
@@ -0,0 +1,195 @@
+//! Checking that data is sorted, and the safe constructors built on it.
+
+use crate::{Sorted, SortedSlice, SortedVec};
+use std::fmt::{self, Debug, Display};
+
+/// The first pair of neighbours found in descending order.
+///
+/// `next` is at `index` and `prev` at `index - 1`,
+/// `next` is less than `prev`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Unsorted<T> {
+    pub index: usize,
+    pub prev: T,
+    pub next: T,
+}
+
+impl<T: Clone> Unsorted<&T> {
+    pub fn cloned(self) -> Unsorted<T> {
+        Unsorted {
+            index: self.index,
+            prev: self.prev.clone(),
+            next: self.next.clone(),
+        }
+    }
+}
+
+impl<T: Debug> Display for Unsorted<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "not sorted at index {}: {:?} is less than the previous {:?}",
+            self.index, self.next, self.prev
+        )
+    }
+}
+
+impl<T: Debug> std::error::Error for Unsorted<T> {}
+
+/// Check that `slice` is in non-descending order by `is_less`
+///
+/// Equal neighbours are accepted.
+///
+/// # Examples
+///
+/// ```
+/// use sort::{verify_by, Unsorted};
+/// assert_eq!(verify_by(&[1, 2, 2, 3], |a, b| a < b), Ok(()));
+/// assert_eq!(
+///     verify_by(&[1, 3, 2, 2], |a, b| a < b),
+///     Err(Unsorted { index: 2, prev: &3, next: &2 })
+/// );
+/// ```
+pub fn verify_by<T, F>(slice: &[T], is_less: F) -> Result<(), Unsorted<&T>>
+where
+    F: FnMut(&T, &T) -> bool,
+{
+    let mut is_less = is_less;
+    match slice.windows(2).position(|w| is_less(&w[1], &w[0])) {
+        Some(i) => Err(Unsorted {
+            index: i + 1,
+            prev: &slice[i],
+            next: &slice[i + 1],
+        }),
+        None => Ok(()),
+    }
+}
+
+impl<T, const N: usize, F: FnMut(&T, &T) -> bool> Sorted<T, N, F> {
+    /// Check the array again with the stored `rule_is_less`.
+    pub fn verify(&self) -> Result<(), Unsorted<&T>> {
+        verify_by(&self.array, &mut *self.rule_is_less.borrow_mut())
+    }
+
+    /// # Examples
+    ///
+    /// ```
+    /// use sort::{Sorted, Unsorted};
+    /// let sorted = Sorted::try_from_array([1, 2, 2, 3], |a, b| a < b).unwrap();
+    /// assert_eq!(*sorted, [1, 2, 2, 3]);
+    ///
+    /// let unsorted = Sorted::try_from_array([2, 1], |a, b| a < b);
+    /// assert_eq!(unsorted.err(), Some(Unsorted { index: 1, prev: 2, next: 1 }));
+    /// ```
+    pub fn try_from_array(array: [T; N], rule_is_less: F) -> Result<Self, Unsorted<T>>
+    where
+        T: Clone,
+    {
+        let mut rule_is_less = rule_is_less;
+        verify_by(&array, &mut rule_is_less).map_err(Unsorted::cloned)?;
+        Ok(unsafe { Sorted::uncheck_from_array(array, rule_is_less) })
+    }
+}
+
+impl<'a, T, F: FnMut(&T, &T) -> bool> SortedSlice<'a, T, F> {
+    /// See [`Sorted::verify`].
+    pub fn verify(&self) -> Result<(), Unsorted<&T>> {
+        verify_by(self.slice, &mut *self.rule_is_less.borrow_mut())
+    }
+
+    /// See [`Sorted::try_from_array`].
+    pub fn try_from_slice(slice: &'a [T], rule_is_less: F) -> Result<Self, Unsorted<&'a T>> {
+        let mut rule_is_less = rule_is_less;
+        verify_by(slice, &mut rule_is_less)?;
+        Ok(unsafe { SortedSlice::uncheck_from_slice(slice, rule_is_less) })
+    }
+}
+
+impl<T, F: FnMut(&T, &T) -> bool> SortedVec<T, F> {
+    /// See [`Sorted::verify`].
+    pub fn verify(&self) -> Result<(), Unsorted<&T>> {
+        verify_by(&self.vec, &mut *self.rule_is_less.borrow_mut())
+    }
+
+    /// See [`Sorted::try_from_array`].
+    pub fn try_from_vec(vec: Vec<T>, rule_is_less: F) -> Result<Self, Unsorted<T>>
+    where
+        T: Clone,
+    {
+        let mut rule_is_less = rule_is_less;
+        verify_by(&vec, &mut rule_is_less).map_err(Unsorted::cloned)?;
+        Ok(unsafe { SortedVec::uncheck_from_vec(vec, rule_is_less) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{read_bench_data, Solution, Stable};
+
+    #[test]
+    fn empty() {
+        assert_eq!(verify_by::<i32, _>(&[], i32::lt), Ok(()));
+        assert_eq!(verify_by(&[1], i32::lt), Ok(()));
+    }
+
+    #[test]
+    fn equal_neighbours() {
+        assert_eq!(verify_by(&[1, 1, 1], i32::lt), Ok(()));
+        assert!(Stable::sort([2, 1, 2, 1]).force_check());
+    }
+
+    #[test]
+    fn first_violation() {
+        let error = verify_by(&[1, 5, 4, 3, 2], i32::lt).unwrap_err();
+        assert_eq!(
+            error,
+            Unsorted {
+                index: 2,
+                prev: &5,
+                next: &4
+            }
+        );
+        assert_eq!(
+            error.to_string(),
+            "not sorted at index 2: 4 is less than the previous 5"
+        );
+    }
+
+    #[test]
+    fn stored_rule() {
+        let sorted = Stable::sort_by([1, 3, 2], |a, b| b.cmp(a));
+        assert_eq!(sorted.verify(), Ok(()));
+        assert!(Sorted::try_from_array([3, 2, 2, 1], |a: &i32, b: &i32| a > b).is_ok());
+        assert!(Sorted::try_from_array([1, 2], |a: &i32, b: &i32| a > b).is_err());
+    }
+
+    #[test]
+    fn slice_and_vec() {
+        let slice = [1, 2, 0];
+        let error = SortedSlice::try_from_slice(&slice, i32::lt).err().unwrap();
+        assert_eq!(
+            error,
+            Unsorted {
+                index: 2,
+                prev: &2,
+                next: &0
+            }
+        );
+        let sorted = SortedVec::try_from_vec(vec![0, 0, 1], i32::lt).unwrap();
+        assert_eq!(sorted.verify(), Ok(()));
+    }
+
+    #[test]
+    fn random() {
+        let data: [i32; 10000] = read_bench_data!("random");
+        let sorted = Stable::sort(data);
+        assert!(sorted.windows(2).any(|w| w[0] == w[1]));
+        assert!(sorted.force_check());
+
+        let mut array = sorted.take();
+        array.swap(0, 9999);
+        let error = Sorted::try_from_array(array, i32::lt).err().unwrap();
+        assert_eq!(error.index, 1);
+    }
+}